@@ -9,7 +9,7 @@ import { ShipPlacement } from "@/components/ShipPlacement";
 import { GamePlay } from "@/components/GamePlay";
 import { Anchor, Zap, Shield, Target, Coins, Loader2, Copy, Check, X, AlertCircle } from "lucide-react";
 import { useFleetWars, GameState as OnChainGameState, TurnState } from "@/hooks/useFleetWars";
-import { BN, bitmaskToShips, GameState } from "@/lib/program";
+import { BN, bitmaskToShips, GameState, isSettled } from "@/lib/program";
 
 type ViewState = "landing" | "lobby" | "placement" | "waiting" | "playing" | "reveal" | "finished";
 type GameMode = "create" | "join" | null;
@@ -172,7 +172,7 @@ export default function Home() {
         }
         
         // Check for game end - WaitingReveal means winner decided, needs reveal
-        if (account.gameState === GameState.WaitingReveal || account.gameState === GameState.Finished) {
+        if (account.gameState === GameState.WaitingReveal || isSettled(account.gameState)) {
           const iWon = (isPlayer1 && account.winner === 1) || (!isPlayer1 && account.winner === 2);
           setWinner(iWon ? "me" : "opponent");
           setViewState("finished");
@@ -221,7 +221,7 @@ export default function Home() {
   const handleRespondHit = useCallback(async (hit: boolean) => {
     if (!activeGame || !canRespond) return;
     
-    const result = await fleetWars.respondShot(activeGame.pda, hit);
+    const result = await fleetWars.respondShot(activeGame.pda, hit, activeGame.ships);
     if (result) {
       showToast(hit ? "Hit confirmed!" : "Miss confirmed!", "info");
     } else {
@@ -248,10 +248,11 @@ export default function Home() {
 
       const gameAccount = await fleetWars.fetchGame(activeGame.pda);
       if (gameAccount?.p1Revealed && gameAccount?.p2Revealed) {
-        const winnerKey = gameAccount.winner === 1 ? gameAccount.player1 : gameAccount.player2;
+        // With no winner (a draw) player1 settles
+        const winnerKey = gameAccount.winner === 2 ? gameAccount.player2 : gameAccount.player1;
         if (winnerKey.equals(publicKey ?? PublicKey.default)) {
           showToast("Finalizing game...", "info");
-          const finalizeResult = await fleetWars.finalize(activeGame.pda, winnerKey);
+          const finalizeResult = await fleetWars.finalize(activeGame.pda, gameAccount);
           if (finalizeResult) {
             showToast("Game finalized! Payout complete.", "success");
           } else {
@@ -277,17 +278,17 @@ export default function Home() {
       const gameAccount = await fleetWars.fetchGame(activeGame.pda);
       if (!gameAccount) return;
 
-      if (gameAccount.gameState === GameState.Finished) {
+      if (isSettled(gameAccount.gameState)) {
         finalizeRef.current = true;
         return;
       }
 
       if (gameAccount.p1Revealed && gameAccount.p2Revealed) {
-        const winnerKey = gameAccount.winner === 1 ? gameAccount.player1 : gameAccount.player2;
+        const winnerKey = gameAccount.winner === 2 ? gameAccount.player2 : gameAccount.player1;
         if (winnerKey.equals(publicKey)) {
           finalizeRef.current = true;
           showToast("Finalizing game...", "info");
-          const finalizeResult = await fleetWars.finalize(activeGame.pda, winnerKey);
+          const finalizeResult = await fleetWars.finalize(activeGame.pda, gameAccount);
           if (finalizeResult) {
             showToast("Game finalized! Payout complete.", "success");
           } else {
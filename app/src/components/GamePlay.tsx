@@ -4,6 +4,7 @@ import { FC, useState } from "react";
 import { motion } from "framer-motion";
 import { GameBoard } from "./GameBoard";
 import { Timer, Zap, Target, Shield } from "lucide-react";
+import { SHIP_CELLS } from "@/lib/program";
 
 interface GamePlayProps {
   gameId: string;
@@ -70,12 +71,12 @@ export const GamePlay: FC<GamePlayProps> = ({
           <div className="flex items-center gap-6">
             <div className="text-center">
               <p className="text-xs text-gray-500 uppercase">Your Hits</p>
-              <p className="text-2xl font-bold text-cyan-400">{myHitCount}/9</p>
+              <p className="text-2xl font-bold text-cyan-400">{myHitCount}/{SHIP_CELLS}</p>
             </div>
             <div className="text-gray-600">VS</div>
             <div className="text-center">
               <p className="text-xs text-gray-500 uppercase">Enemy Hits</p>
-              <p className="text-2xl font-bold text-pink-400">{opponentHitCount}/9</p>
+              <p className="text-2xl font-bold text-pink-400">{opponentHitCount}/{SHIP_CELLS}</p>
             </div>
           </div>
         </div>
@@ -176,7 +177,7 @@ export const GamePlay: FC<GamePlayProps> = ({
         <ul className="list-disc list-inside space-y-1">
           <li>When it says <span className="text-green-400">YOUR TURN</span>, click a cell on the enemy board and press Fire.</li>
           <li>When you see <span className="text-pink-400">Enemy fired at ...</span>, choose Hit or Miss to respond.</li>
-          <li>First player to reach <span className="text-cyan-400">9 hits</span> wins. The game then enters reveal phase.</li>
+          <li>First player to reach <span className="text-cyan-400">{SHIP_CELLS} hits</span> wins. The game then enters reveal phase.</li>
           <li>Click <span className="text-green-400">Reveal Board</span> at the end to prove your hits and claim rewards.</li>
         </ul>
       </div>
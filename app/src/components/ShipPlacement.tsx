@@ -5,6 +5,7 @@ import { motion } from "framer-motion";
 import { cn } from "@/lib/utils";
 import { RotateCw, Trash2, Sparkles } from "lucide-react";
 import { ShipSkin, SHIP_SKINS } from "@/hooks/useShipNFTs";
+import { FLEET, SHIP_CELLS } from "@/lib/program";
 
 interface ShipPlacementProps {
   onBoardComplete: (board: bigint) => void;
@@ -20,12 +21,12 @@ const SHIPS = [
   { name: "Destroyer", size: 2 },
 ];
 
-// Total cells: 5+4+3+3+2 = 17, but game uses 9 cells
-const GAME_SHIPS = [
-  { name: "Destroyer", size: 3 },
-  { name: "Cruiser", size: 3 },
-  { name: "Submarine", size: 3 },
-];
+// Place exactly the on-chain fleet, named after the classic ship of the same size
+const GAME_SHIPS = FLEET.map((size, index) => ({
+  id: index,
+  name: SHIPS.find((ship) => ship.size === size)?.name ?? `Ship ${index + 1}`,
+  size,
+}));
 
 export const ShipPlacement: FC<ShipPlacementProps> = ({ onBoardComplete, onSkinSelect, selectedSkin }) => {
   const currentSkin = selectedSkin || SHIP_SKINS.default;
@@ -120,7 +121,10 @@ export const ShipPlacement: FC<ShipPlacementProps> = ({ onBoardComplete, onSkinS
         <ul className="list-disc list-inside space-y-1">
           <li>Click any grid cell to place the highlighted ship.</li>
           <li>Use <span className="text-cyan-400">Rotate</span> to switch horizontal/vertical.</li>
-          <li>Place 3 ships of 3 cells each (total 9 ship cells).</li>
+          <li>
+            Place {GAME_SHIPS.length} ships of {FLEET.join(", ")} cells (total {SHIP_CELLS} ship
+            cells).
+          </li>
           <li>Press <span className="text-green-400">Ready for Battle</span> once all ships are placed.</li>
         </ul>
       </div>
@@ -196,7 +200,7 @@ export const ShipPlacement: FC<ShipPlacementProps> = ({ onBoardComplete, onSkinS
       <div className="flex gap-2">
         {GAME_SHIPS.map((ship, index) => (
           <div
-            key={ship.name}
+            key={ship.id}
             className={cn(
               "px-3 py-1 rounded text-xs font-bold border",
               index < currentShipIndex
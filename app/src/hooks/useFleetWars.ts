@@ -7,11 +7,13 @@ import { AnchorProvider, BN } from "@coral-xyz/anchor";
 import {
   getProgram,
   getGamePda,
+  getProfilePda,
   getBufferPda,
   getDelegationRecordPda,
   getDelegationMetadataPda,
   computeBoardHash,
   shipsToBitmask,
+  shotResult,
   generateSalt,
   GameAccount,
   GameState,
@@ -22,6 +24,13 @@ import {
   MAGIC_PROGRAM,
   ER_VALIDATOR,
   MAGIC_ROUTER_RPC,
+  BOARD_ROWS,
+  BOARD_COLS,
+  BOARD_CELLS,
+  FLEET,
+  SHIP_CELLS,
+  TURN_TIMEOUT_SECS,
+  COMMIT_FREQUENCY_MS,
 } from "@/lib/program";
 
 export { GameState, TurnState };
@@ -138,8 +147,8 @@ export function useFleetWars() {
         return null;
       }
 
-      if (ships.length !== 9) {
-        setError("Must place exactly 9 ship cells");
+      if (ships.length !== SHIP_CELLS) {
+        setError(`Must place exactly ${SHIP_CELLS} ship cells`);
         return null;
       }
 
@@ -149,10 +158,10 @@ export function useFleetWars() {
       try {
         const program = getProgram(baseProvider);
         
-        // Generate random game ID
-        const gameIdBytes = new Uint8Array(8);
-        crypto.getRandomValues(gameIdBytes);
-        const gameId = new BN(gameIdBytes, "le");
+        // Game ids come from the creator's profile counter (0 before their first game)
+        const [profilePda] = getProfilePda(publicKey);
+        const profile = await program.account.playerProfile.fetchNullable(profilePda);
+        const gameId = profile ? profile.gamesCreated : new BN(0);
 
         // Compute board hash
         const boardBits = shipsToBitmask(ships);
@@ -161,11 +170,29 @@ export function useFleetWars() {
 
         const [gamePda] = getGamePda(publicKey, gameId);
 
+        const rules = {
+          turnTimeoutSecs: new BN(TURN_TIMEOUT_SECS),
+          commitmentMode: 0, // flat SHA256 board hash, no Merkle proofs
+          fleet: Buffer.from(FLEET),
+          boardRows: BOARD_ROWS,
+          boardCols: BOARD_COLS,
+          gameMode: 0, // classic, one shot per turn
+          bond: new BN(0),
+          rated: true,
+        };
+
         const signature = await program.methods
-          .createGame(gameId, Array.from(boardHash), new BN(wagerLamports))
+          .createGame(Array.from(boardHash), new BN(wagerLamports), rules, null)
           .accountsPartial({
+            profile: profilePda,
             game: gamePda,
             player1: publicKey,
+            // lamport wager held on the game PDA; not listed in a lobby page
+            mint: null,
+            vault: null,
+            player1Token: null,
+            tokenProgram: null,
+            lobby: null,
           })
           .rpc();
 
@@ -225,9 +252,9 @@ export function useFleetWars() {
         const [delegationMetadataPda] = getDelegationMetadataPda(gamePda);
 
         const signature = await program.methods
-          .delegateGame(gameId)
+          .delegateGame(gameId, COMMIT_FREQUENCY_MS)
           .accountsPartial({
-            player1: publicKey,
+            player: publicKey,
             validator: ER_VALIDATOR,
             bufferPda: bufferPda,
             delegationRecordPda: delegationRecordPda,
@@ -261,8 +288,8 @@ export function useFleetWars() {
         return null;
       }
 
-      if (ships.length !== 9) {
-        setError("Must place exactly 9 ship cells");
+      if (ships.length !== SHIP_CELLS) {
+        setError(`Must place exactly ${SHIP_CELLS} ship cells`);
         return null;
       }
 
@@ -283,6 +310,10 @@ export function useFleetWars() {
             game: gamePda,
             player2: publicKey,
             systemProgram: SystemProgram.programId,
+            vault: null,
+            player2Token: null,
+            tokenProgram: null,
+            lobby: null,
           })
           .rpc();
 
@@ -308,8 +339,8 @@ export function useFleetWars() {
         return null;
       }
 
-      if (cell < 0 || cell > 63) {
-        setError("Invalid cell (must be 0-63)");
+      if (cell < 0 || cell >= BOARD_CELLS) {
+        setError(`Invalid cell (must be 0-${BOARD_CELLS - 1})`);
         return null;
       }
 
@@ -336,6 +367,7 @@ export function useFleetWars() {
                 .accounts({
                   game: gamePda,
                   player: publicKey,
+                  session: null,
                 })
                 .transaction();
 
@@ -376,6 +408,7 @@ export function useFleetWars() {
               .accounts({
                 game: gamePda,
                 player: publicKey,
+                session: null,
               })
               .rpc();
 
@@ -398,9 +431,10 @@ export function useFleetWars() {
     [erProvider, baseProvider, publicKey, connection, sendErTransaction]
   );
 
-  // Respond to shot - try ER first, fallback to L1
+  // Respond to shot on the ER. The battle phase is ER-only: the response that ends the game
+  // commits and undelegates it, which only the ER can do, so there is no L1 fallback.
   const respondShot = useCallback(
-    async (gamePda: PublicKey, hit: boolean): Promise<string | null> => {
+    async (gamePda: PublicKey, hit: boolean, ships: number[]): Promise<string | null> => {
       if (!publicKey) {
         setError("Wallet not connected");
         return null;
@@ -416,79 +450,54 @@ export function useFleetWars() {
           return null;
         }
 
-        const isDelegated = accountInfo.owner.equals(DELEGATION_PROGRAM_ID);
-        const canUseL1 = accountInfo.owner.equals(FLEET_WARS_PROGRAM_ID);
-
-        // Try ER first
-        if (erProvider) {
-          try {
-            const program = getProgram(erProvider);
-            const buildTx = () =>
-              program.methods
-                .respondShot(hit)
-                .accounts({
-                  game: gamePda,
-                  player: publicKey,
-                })
-                .transaction();
-
-            let signature: string;
-            try {
-              signature = await sendErTransaction(await buildTx());
-            } catch (erErr: unknown) {
-              if (isBlockhashNotFound(erErr)) {
-                await sleep(500);
-                signature = await sendErTransaction(await buildTx());
-              } else {
-                throw erErr;
-              }
-            }
-
-            console.log("Responded on ER, hit:", hit, "tx:", signature);
-            return signature;
-          } catch (erErr: unknown) {
-            if (isDelegated) {
-              const message = erErr instanceof Error ? erErr.message : "Failed to respond on ER";
-              console.error("ER respond shot failed:", erErr);
-              setError(message);
-              return null;
-            }
-            console.warn("ER respond shot failed, trying L1:", erErr);
-          }
-        } else if (isDelegated) {
+        if (!accountInfo.owner.equals(DELEGATION_PROGRAM_ID)) {
+          setError("Game is not delegated to the ER");
+          return null;
+        }
+        if (!erProvider) {
           setError("Game is delegated, but ER provider is unavailable");
           return null;
         }
 
-        // Fallback to L1 only if account is owned by Fleet Wars program
-        if (canUseL1 && baseProvider) {
-          try {
-            const program = getProgram(baseProvider);
-            const signature = await program.methods
-              .respondShot(hit)
-              .accounts({
-                game: gamePda,
-                player: publicKey,
-              })
-              .rpc();
+        const program = getProgram(erProvider);
 
-            console.log("Responded on L1, hit:", hit, "tx:", signature);
-            return signature;
-          } catch (err: unknown) {
-            const message = err instanceof Error ? err.message : "Failed to respond to shot";
-            console.error("Respond shot error:", err);
-            setError(message);
-            return null;
-          }
+        // Sunk ships are announced with the hit that completes them
+        const game = await program.account.game.fetch(gamePda);
+        const result = shotResult(ships, game as unknown as GameAccount, hit);
+
+        const buildTx = () =>
+          program.methods
+            .respondShot(result, null)
+            .accountsPartial({
+              game: gamePda,
+              player: publicKey,
+              session: null,
+              magicContext: MAGIC_CONTEXT,
+              magicProgram: MAGIC_PROGRAM,
+            })
+            .transaction();
+
+        let signature: string;
+        try {
+          signature = await sendErTransaction(await buildTx());
+        } catch (erErr: unknown) {
+          if (!isBlockhashNotFound(erErr)) throw erErr;
+          await sleep(500);
+          signature = await sendErTransaction(await buildTx());
         }
 
-        setError("No provider available for current game state");
+        console.log("Responded on ER, hit:", hit, "tx:", signature);
+        return signature;
+      } catch (err: unknown) {
+        const message = err instanceof Error ? err.message : "Failed to respond on ER";
+        console.error("ER respond shot failed:", err);
+        setError(message);
         return null;
       } finally {
         setLoading(false);
       }
     },
-    [erProvider, baseProvider, publicKey, connection]
+    [erProvider, publicKey, connection, sendErTransaction]
   );
 
   // End session (manual undelegate)
@@ -572,9 +581,9 @@ export function useFleetWars() {
     [baseProvider, publicKey]
   );
 
-  // Finalize game on L1
+  // Finalize game on L1: pays both players (winnings, draw refund or bond) and the treasury fee
   const finalize = useCallback(
-    async (gamePda: PublicKey, winnerPubkey: PublicKey): Promise<string | null> => {
+    async (gamePda: PublicKey, game: GameAccount): Promise<string | null> => {
       if (!baseProvider || !publicKey) {
         setError("Wallet not connected");
         return null;
@@ -588,10 +597,19 @@ export function useFleetWars() {
 
         const signature = await program.methods
           .finalize()
-          .accounts({
+          .accountsPartial({
             game: gamePda,
-            winner: winnerPubkey,
+            p1Payout: game.player1,
+            p2Payout: game.player2,
             caller: publicKey,
+            p1Profile: getProfilePda(game.player1)[0],
+            p2Profile: getProfilePda(game.player2)[0],
+            // keep the game account open (no auto-close)
+            player1: null,
+            // lamport game: the fee lands on the treasury PDA itself
+            treasuryToken: null,
+            vault: null,
+            tokenProgram: null,
           })
           .rpc();
 
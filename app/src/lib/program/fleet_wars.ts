@@ -14,291 +14,334 @@ export type FleetWars = {
   },
   "instructions": [
     {
-      "name": "createGame",
+      "name": "cancelGame",
       "docs": [
-        "Phase 1 — L1: Player1 creates a game, commits their board hash, deposits wager."
+        "Phase 1 — L1: Player1 cancels an unjoined game; wager and rent are refunded."
       ],
       "discriminator": [
-        124,
-        69,
-        75,
-        66,
-        184,
-        220,
-        72,
-        206
+        121,
+        194,
+        154,
+        118,
+        103,
+        235,
+        149,
+        52
       ],
       "accounts": [
         {
           "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player1",
           "writable": true,
+          "signer": true,
+          "relations": [
+            "game"
+          ]
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  103,
+                  118,
                   97,
-                  109,
-                  101
+                  117,
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "player1"
-              },
-              {
-                "kind": "arg",
-                "path": "gameId"
+                "path": "game"
               }
             ]
           }
         },
         {
-          "name": "player1",
+          "name": "player1Token",
           "writable": true,
-          "signer": true
+          "optional": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — the lobby page listing this game, required when `game.lobby` is set"
+          ],
+          "writable": true,
+          "optional": true
         }
       ],
-      "args": [
+      "args": []
+    },
+    {
+      "name": "checkpointGame",
+      "docs": [
+        "Phase 2 — ER: Either player commits the current game state to L1 while staying",
+        "delegated."
+      ],
+      "discriminator": [
+        89,
+        211,
+        138,
+        212,
+        195,
+        142,
+        178,
+        122
+      ],
+      "accounts": [
         {
-          "name": "gameId",
-          "type": "u64"
+          "name": "game",
+          "writable": true
         },
         {
-          "name": "boardHash",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
+          "name": "player",
+          "writable": true,
+          "signer": true
         },
         {
-          "name": "wager",
-          "type": "u64"
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "delegateGame",
+      "name": "claimTimeout",
       "docs": [
-        "Phase 1 — L1: Player1 delegates the game account to MagicBlock Ephemeral Rollup."
+        "Phase 2 — ER: Waiting player claims the win after the opponent misses their turn deadline,",
+        "undelegating the game back to L1."
       ],
       "discriminator": [
-        116,
-        183,
-        70,
-        107,
-        112,
-        223,
-        122,
-        210
+        130,
+        234,
+        45,
+        53,
+        120,
+        90,
+        86,
+        178
       ],
       "accounts": [
         {
-          "name": "player1",
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
           "writable": true,
           "signer": true
         },
         {
-          "name": "validator",
-          "optional": true
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
         },
         {
-          "name": "bufferPda",
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimUnrevealedForfeit",
+      "docs": [
+        "Phase 3 — L1: After the reveal window closes, marks every non-revealer as a cheater.",
+        "Pays the full pot to a lone revealer whose board passes the finalize checks, otherwise",
+        "voids the game."
+      ],
+      "discriminator": [
+        200,
+        46,
+        238,
+        20,
+        122,
+        117,
+        151,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "p1Payout",
+          "writable": true
+        },
+        {
+          "name": "p2Payout",
+          "writable": true
+        },
+        {
+          "name": "caller",
+          "docs": [
+            "Pays for any player profile created here"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "p1Profile",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
-                  117,
-                  102,
+                  112,
+                  114,
+                  111,
                   102,
-                  101,
-                  114
+                  105,
+                  108,
+                  101
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "game.player1",
+                "account": "game"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                188,
-                239,
-                109,
-                155,
-                161,
-                35,
-                107,
-                125,
-                162,
-                216,
-                132,
-                38,
-                196,
-                170,
-                53,
-                71,
-                199,
-                79,
-                25,
-                229,
-                184,
-                67,
-                244,
-                225,
-                187,
-                234,
-                236,
-                194,
-                169,
-                37,
-                212,
-                7
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "delegationRecordPda",
+          "name": "p2Profile",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
-                  108,
-                  101,
-                  103,
-                  97,
-                  116,
-                  105,
+                  112,
+                  114,
                   111,
-                  110
+                  102,
+                  105,
+                  108,
+                  101
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "game.player2",
+                "account": "game"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegationProgram"
-            }
+            ]
           }
         },
         {
-          "name": "delegationMetadataPda",
-          "writable": true,
+          "name": "config",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
-                  108,
-                  101,
-                  103,
-                  97,
-                  116,
-                  105,
+                  99,
                   111,
                   110,
-                  45,
-                  109,
-                  101,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
                   116,
+                  114,
+                  101,
                   97,
-                  100,
-                  97,
-                  116,
-                  97
+                  115,
+                  117,
+                  114,
+                  121
                 ]
-              },
-              {
-                "kind": "account",
-                "path": "pda"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegationProgram"
-            }
+            ]
           }
         },
         {
-          "name": "pda",
+          "name": "treasuryToken",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "vault",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  103,
+                  118,
                   97,
-                  109,
-                  101
+                  117,
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "player1"
-              },
-              {
-                "kind": "arg",
-                "path": "gameId"
+                "path": "game"
               }
             ]
           }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "ownerProgram",
-          "address": "DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn"
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         },
         {
-          "name": "delegationProgram",
-          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "gameId",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "endSession",
+      "name": "closeGame",
       "docs": [
-        "Phase 2 — ER: Manually end the ER session (e.g. opponent abandoned)."
+        "Phase 3 — L1: Closes a finished game and returns its rent to player1."
       ],
       "discriminator": [
-        11,
-        244,
-        61,
-        154,
-        212,
-        249,
-        15,
-        66
+        237,
+        236,
+        157,
+        201,
+        253,
+        20,
+        248,
+        67
       ],
       "accounts": [
         {
@@ -306,113 +349,173 @@ export type FleetWars = {
           "writable": true
         },
         {
-          "name": "player",
+          "name": "player1",
           "writable": true,
+          "relations": [
+            "game"
+          ]
+        },
+        {
+          "name": "caller",
           "signer": true
         },
         {
-          "name": "magicContext",
+          "name": "vault",
           "writable": true,
-          "address": "MagicContext1111111111111111111111111111111"
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              }
+            ]
+          }
         },
         {
-          "name": "magicProgram",
-          "address": "Magic11111111111111111111111111111111111111"
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
       ],
       "args": []
     },
     {
-      "name": "finalize",
+      "name": "createGame",
       "docs": [
-        "Phase 3 — L1: Verifies both reveals, detects cheaters, pays winner."
+        "Phase 1 — L1: Player1 creates a game, commits their board hash, deposits wager,",
+        "and fixes the ruleset: per-move timeout, commitment mode (flat hash or per-cell",
+        "Merkle root), fleet lengths and board dimensions (up to 128 cells, e.g. 10x10).",
+        "The game id is the next value of player1's profile counter. With `invited` set,",
+        "only that player may join."
       ],
       "discriminator": [
-        171,
-        61,
-        218,
-        56,
-        127,
-        115,
-        12,
-        217
+        124,
+        69,
+        75,
+        66,
+        184,
+        220,
+        72,
+        206
       ],
       "accounts": [
         {
-          "name": "game",
-          "writable": true
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "player1"
+              }
+            ]
+          }
         },
         {
-          "name": "winner",
-          "writable": true
+          "name": "game",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  97,
+                  109,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "player1"
+              },
+              {
+                "kind": "account",
+                "path": "profile.games_created",
+                "account": "playerProfile"
+              }
+            ]
+          }
         },
         {
-          "name": "caller",
+          "name": "player1",
+          "writable": true,
           "signer": true
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "fireShot",
-      "docs": [
-        "Phase 2 — ER: Active player fires a shot at a cell (0–63)."
-      ],
-      "discriminator": [
-        66,
-        150,
-        104,
-        42,
-        242,
-        254,
-        17,
-        199
-      ],
-      "accounts": [
-        {
-          "name": "game",
-          "writable": true
         },
         {
-          "name": "player",
-          "signer": true
-        }
-      ],
-      "args": [
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
         {
-          "name": "cell",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "joinGame",
-      "docs": [
-        "Phase 1 — L1: Player2 joins, commits their board hash, deposits wager."
-      ],
-      "discriminator": [
-        107,
-        112,
-        18,
-        38,
-        56,
-        173,
-        60,
-        128
-      ],
-      "accounts": [
+          "name": "mint",
+          "docs": [
+            "Optional — set to wager an SPL token instead of lamports"
+          ],
+          "optional": true
+        },
         {
-          "name": "game",
-          "writable": true
+          "name": "vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              }
+            ]
+          }
         },
         {
-          "name": "player2",
+          "name": "player1Token",
           "writable": true,
-          "signer": true
+          "optional": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — lobby page of the wager's bracket to list the game in"
+          ],
+          "writable": true,
+          "optional": true
         }
       ],
       "args": [
@@ -424,280 +527,2543 @@ export type FleetWars = {
               32
             ]
           }
-        }
-      ]
-    },
-    {
-      "name": "processUndelegation",
-      "discriminator": [
-        196,
-        28,
-        41,
-        206,
-        48,
-        37,
-        51,
-        167
-      ],
-      "accounts": [
-        {
-          "name": "baseAccount",
-          "writable": true
         },
         {
-          "name": "buffer"
+          "name": "wager",
+          "type": "u64"
         },
         {
-          "name": "payer",
-          "writable": true
+          "name": "rules",
+          "type": {
+            "defined": {
+              "name": "gameRules"
+            }
+          }
         },
         {
-          "name": "systemProgram"
-        }
-      ],
-      "args": [
-        {
-          "name": "accountSeeds",
+          "name": "invited",
           "type": {
-            "vec": "bytes"
+            "option": "pubkey"
           }
         }
       ]
     },
     {
-      "name": "respondShot",
+      "name": "createSession",
       "docs": [
-        "Phase 2 — ER: Opponent declares hit or miss for the last shot.",
-        "Auto-undelegates back to L1 when all ships are sunk."
+        "Phase 1 — L1: A player authorizes an ephemeral session key to sign their ER moves in",
+        "one game, with an expiry and a capped fee top-up."
       ],
       "discriminator": [
-        59,
-        59,
-        219,
-        146,
-        86,
-        104,
-        104,
-        45
+        242,
+        193,
+        143,
+        179,
+        150,
+        25,
+        122,
+        227
       ],
       "accounts": [
         {
-          "name": "game",
-          "writable": true
+          "name": "game"
         },
         {
-          "name": "player",
+          "name": "session",
           "writable": true,
-          "signer": true
-        }
-      ],
-      "args": [
-        {
-          "name": "hit",
-          "type": "bool"
-        }
-      ]
-    },
-    {
-      "name": "revealBoard",
-      "docs": [
-        "Phase 3 — L1: Each player reveals their actual board + salt to prove honesty."
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "sessionSigner",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sessionKey",
+          "type": "pubkey"
+        },
+        {
+          "name": "expiresAt",
+          "type": "i64"
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "delegateGame",
+      "docs": [
+        "Phase 1 — L1: Either player delegates the game account to MagicBlock Ephemeral Rollup.",
+        "The commit frequency and validator must satisfy the config's delegation policy."
       ],
       "discriminator": [
-        24,
-        194,
-        178,
-        9,
-        120,
-        113,
-        199,
-        252
+        116,
+        183,
+        70,
+        107,
+        112,
+        223,
+        122,
+        210
       ],
       "accounts": [
-        {
-          "name": "game",
-          "writable": true
-        },
         {
           "name": "player",
+          "docs": [
+            "Either player; pays for the delegation accounts"
+          ],
+          "writable": true,
           "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validator",
+          "optional": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                188,
+                239,
+                109,
+                155,
+                161,
+                35,
+                107,
+                125,
+                162,
+                216,
+                132,
+                38,
+                196,
+                170,
+                53,
+                71,
+                199,
+                79,
+                25,
+                229,
+                184,
+                67,
+                244,
+                225,
+                187,
+                234,
+                236,
+                194,
+                169,
+                37,
+                212,
+                7
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  97,
+                  109,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda.player1",
+                "account": "game"
+              },
+              {
+                "kind": "arg",
+                "path": "gameId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "ownerProgram",
+          "address": "DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
         }
       ],
       "args": [
         {
-          "name": "board",
+          "name": "gameId",
           "type": "u64"
         },
         {
-          "name": "salt",
-          "type": {
-            "array": [
-              "u8",
-              32
+          "name": "commitFrequencyMs",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "endSession",
+      "docs": [
+        "Phase 2 — ER: Manually end the ER session (e.g. opponent abandoned). A game still in",
+        "battle moves to the reveal phase; the caller forfeits unless the opponent timed out."
+      ],
+      "discriminator": [
+        11,
+        244,
+        61,
+        154,
+        212,
+        249,
+        15,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "expireGame",
+      "docs": [
+        "Phase 1 — L1: Anyone refunds a stale unjoined game to its creator after the lobby expiry."
+      ],
+      "discriminator": [
+        208,
+        21,
+        87,
+        131,
+        242,
+        43,
+        252,
+        144
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player1",
+          "writable": true,
+          "relations": [
+            "game"
+          ]
+        },
+        {
+          "name": "caller",
+          "signer": true
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              }
+            ]
+          }
+        },
+        {
+          "name": "player1Token",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — the lobby page listing this game, required when `game.lobby` is set"
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finalize",
+      "docs": [
+        "Phase 3 — L1: Verifies both reveals, detects cheaters, pays winner.",
+        "Closes the game to player1 when the optional `player1` account is supplied."
+      ],
+      "discriminator": [
+        171,
+        61,
+        218,
+        56,
+        127,
+        115,
+        12,
+        217
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "p1Payout",
+          "writable": true
+        },
+        {
+          "name": "p2Payout",
+          "writable": true
+        },
+        {
+          "name": "caller",
+          "docs": [
+            "Pays for any player profile created here"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "p1Profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game.player1",
+                "account": "game"
+              }
+            ]
+          }
+        },
+        {
+          "name": "p2Profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game.player2",
+                "account": "game"
+              }
+            ]
+          }
+        },
+        {
+          "name": "player1",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  101,
+                  97,
+                  115,
+                  117,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasuryToken",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              }
             ]
           }
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "game",
-      "discriminator": [
-        27,
-        90,
-        166,
-        125,
-        74,
-        100,
-        121,
-        18
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidGameState",
-      "msg": "Game is not in the expected state"
-    },
-    {
-      "code": 6001,
-      "name": "notYourTurn",
-      "msg": "Not your turn"
-    },
-    {
-      "code": 6002,
-      "name": "cellAlreadyShot",
-      "msg": "Cell already shot"
-    },
-    {
-      "code": 6003,
-      "name": "invalidCell",
-      "msg": "Invalid cell — out of bounds (0–63)"
-    },
-    {
-      "code": 6004,
-      "name": "gameNotActive",
-      "msg": "Game is not active"
-    },
-    {
-      "code": 6005,
-      "name": "boardHashMismatch",
-      "msg": "Board hash mismatch — cheater detected"
-    },
-    {
-      "code": 6006,
-      "name": "invalidBoard",
-      "msg": "Invalid board: must have exactly 9 ship cells"
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fireSalvo",
+      "docs": [
+        "Phase 2 — ER: Salvo mode — active player fires one shot per ship still afloat."
+      ],
+      "discriminator": [
+        236,
+        127,
+        231,
+        20,
+        34,
+        76,
+        183,
+        216
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "signer": true
+        },
+        {
+          "name": "session",
+          "docs": [
+            "Optional — when present, `player` is this session's key acting for its authority"
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "cells",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "fireShot",
+      "docs": [
+        "Phase 2 — ER: Active player fires a shot at a cell (0..rows * cols)."
+      ],
+      "discriminator": [
+        66,
+        150,
+        104,
+        42,
+        242,
+        254,
+        17,
+        199
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "signer": true
+        },
+        {
+          "name": "session",
+          "docs": [
+            "Optional — when present, `player` is this session's key acting for its authority"
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "cell",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "forfeitReveal",
+      "docs": [
+        "Phase 3 — L1: A player who cannot produce a matching reveal concedes, awarding",
+        "the opponent and letting finalize proceed without that board."
+      ],
+      "discriminator": [
+        253,
+        7,
+        6,
+        55,
+        52,
+        91,
+        213,
+        29
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initConfig",
+      "docs": [
+        "Admin — L1: Upgrade authority creates the protocol config and fee treasury."
+      ],
+      "discriminator": [
+        23,
+        235,
+        115,
+        232,
+        168,
+        96,
+        1,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  101,
+                  97,
+                  115,
+                  117,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "program",
+          "address": "DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "treasuryAuthority",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "initLobbyPage",
+      "docs": [
+        "Phase 1 — L1: Anyone opens a new lobby page for a wager bracket."
+      ],
+      "discriminator": [
+        104,
+        164,
+        114,
+        85,
+        186,
+        184,
+        175,
+        195
+      ],
+      "accounts": [
+        {
+          "name": "lobby",
+          "writable": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "bracket",
+          "type": "u8"
+        },
+        {
+          "name": "page",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "joinAndDelegate",
+      "docs": [
+        "Phase 1 — L1: Player2 joins and delegates the game to the ER in one transaction."
+      ],
+      "discriminator": [
+        254,
+        142,
+        92,
+        201,
+        252,
+        53,
+        127,
+        69
+      ],
+      "accounts": [
+        {
+          "name": "player2",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validator",
+          "optional": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                188,
+                239,
+                109,
+                155,
+                161,
+                35,
+                107,
+                125,
+                162,
+                216,
+                132,
+                38,
+                196,
+                170,
+                53,
+                71,
+                199,
+                79,
+                25,
+                229,
+                184,
+                67,
+                244,
+                225,
+                187,
+                234,
+                236,
+                194,
+                169,
+                37,
+                212,
+                7
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  97,
+                  109,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda.player1",
+                "account": "game"
+              },
+              {
+                "kind": "account",
+                "path": "pda.game_id",
+                "account": "game"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ]
+          }
+        },
+        {
+          "name": "player2Token",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — the lobby page listing this game, required when `game.lobby` is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "ownerProgram",
+          "address": "DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        }
+      ],
+      "args": [
+        {
+          "name": "boardHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "commitFrequencyMs",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "joinGame",
+      "docs": [
+        "Phase 1 — L1: Player2 joins, commits their board hash, deposits wager."
+      ],
+      "discriminator": [
+        107,
+        112,
+        18,
+        38,
+        56,
+        173,
+        60,
+        128
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player2",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "game"
+              }
+            ]
+          }
+        },
+        {
+          "name": "player2Token",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — the lobby page listing this game, required when `game.lobby` is set"
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "boardHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "processUndelegation",
+      "discriminator": [
+        196,
+        28,
+        41,
+        206,
+        48,
+        37,
+        51,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "baseAccount",
+          "writable": true
+        },
+        {
+          "name": "buffer"
+        },
+        {
+          "name": "payer",
+          "writable": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "accountSeeds",
+          "type": {
+            "vec": "bytes"
+          }
+        }
+      ]
+    },
+    {
+      "name": "reopenGame",
+      "docs": [
+        "Phase 1 — L1: Player1 drops the invite so anyone may join the game, optionally listing",
+        "it in a lobby page."
+      ],
+      "discriminator": [
+        208,
+        177,
+        123,
+        143,
+        244,
+        40,
+        165,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player1",
+          "signer": true,
+          "relations": [
+            "game"
+          ]
+        },
+        {
+          "name": "lobby",
+          "docs": [
+            "Optional — lobby page of the wager's bracket to list the game in"
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "respondSalvo",
+      "docs": [
+        "Phase 2 — ER: Salvo mode — opponent answers every shot of the pending salvo,",
+        "in ascending cell order. Undelegates the game when the salvo ends it."
+      ],
+      "discriminator": [
+        216,
+        200,
+        38,
+        181,
+        142,
+        17,
+        126,
+        148
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "session",
+          "docs": [
+            "Optional — when present, `player` is this session's key acting for its authority"
+          ],
+          "optional": true
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "results",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "shotResult"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "respondShot",
+      "docs": [
+        "Phase 2 — ER: Opponent declares miss, hit or sunk(ship_id) for the last shot.",
+        "In Merkle mode the answer must carry an inclusion proof for the shot cell.",
+        "The answer that sinks the last ship commits and undelegates the game back to L1."
+      ],
+      "discriminator": [
+        59,
+        59,
+        219,
+        146,
+        86,
+        104,
+        104,
+        45
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "session",
+          "docs": [
+            "Optional — when present, `player` is this session's key acting for its authority"
+          ],
+          "optional": true
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "result",
+          "type": {
+            "defined": {
+              "name": "shotResult"
+            }
+          }
+        },
+        {
+          "name": "proof",
+          "type": {
+            "option": {
+              "defined": {
+                "name": "cellProof"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "revealBoard",
+      "docs": [
+        "Phase 3 — L1: Each player reveals their actual board + salt to prove honesty."
+      ],
+      "discriminator": [
+        24,
+        194,
+        178,
+        9,
+        120,
+        113,
+        199,
+        252
+      ],
+      "accounts": [
+        {
+          "name": "game",
+          "writable": true
+        },
+        {
+          "name": "player",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "board",
+          "type": "u128"
+        },
+        {
+          "name": "salt",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "revokeSession",
+      "docs": [
+        "Phase 1 — L1: A player revokes their session key for a game."
+      ],
+      "discriminator": [
+        86,
+        92,
+        198,
+        120,
+        144,
+        2,
+        7,
+        194
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.game",
+                "account": "sessionToken"
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "session"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setDelegationPolicy",
+      "docs": [
+        "Admin — L1: Config admin sets delegation commit frequency bounds and the ER",
+        "validator allowlist."
+      ],
+      "discriminator": [
+        183,
+        169,
+        113,
+        75,
+        185,
+        8,
+        82,
+        152
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "minCommitFrequencyMs",
+          "type": "u32"
+        },
+        {
+          "name": "maxCommitFrequencyMs",
+          "type": "u32"
+        },
+        {
+          "name": "allowedValidators",
+          "type": {
+            "vec": "pubkey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateConfig",
+      "docs": [
+        "Admin — L1: Config admin updates the fee, treasury authority or admin."
+      ],
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "pubkey"
+        },
+        {
+          "name": "treasuryAuthority",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "withdrawTreasury",
+      "docs": [
+        "Admin — L1: Treasury authority withdraws collected fees (lamports or tokens)."
+      ],
+      "discriminator": [
+        40,
+        63,
+        122,
+        158,
+        144,
+        216,
+        83,
+        96
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  101,
+                  97,
+                  115,
+                  117,
+                  114,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasuryAuthority",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "destination",
+          "writable": true
+        },
+        {
+          "name": "treasuryToken",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "game",
+      "discriminator": [
+        27,
+        90,
+        166,
+        125,
+        74,
+        100,
+        121,
+        18
+      ]
+    },
+    {
+      "name": "lobbyPage",
+      "discriminator": [
+        227,
+        153,
+        86,
+        64,
+        12,
+        102,
+        179,
+        248
+      ]
+    },
+    {
+      "name": "playerProfile",
+      "discriminator": [
+        82,
+        226,
+        99,
+        87,
+        164,
+        130,
+        181,
+        80
+      ]
+    },
+    {
+      "name": "sessionToken",
+      "discriminator": [
+        233,
+        4,
+        115,
+        14,
+        46,
+        21,
+        1,
+        15
+      ]
+    },
+    {
+      "name": "treasury",
+      "discriminator": [
+        238,
+        239,
+        123,
+        238,
+        89,
+        1,
+        168,
+        253
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidGameState",
+      "msg": "Game is not in the expected state"
+    },
+    {
+      "code": 6001,
+      "name": "notYourTurn",
+      "msg": "Not your turn"
+    },
+    {
+      "code": 6002,
+      "name": "cellAlreadyShot",
+      "msg": "Cell already shot"
+    },
+    {
+      "code": 6003,
+      "name": "invalidCell",
+      "msg": "Invalid cell — outside the game board"
+    },
+    {
+      "code": 6004,
+      "name": "gameNotActive",
+      "msg": "Game is not active"
+    },
+    {
+      "code": 6005,
+      "name": "boardHashMismatch",
+      "msg": "Board hash mismatch — cheater detected"
+    },
+    {
+      "code": 6006,
+      "name": "invalidBoard",
+      "msg": "Invalid board: ships must be straight, non-overlapping and match the fleet"
+    },
+    {
+      "code": 6007,
+      "name": "gameNotReady",
+      "msg": "Both players must reveal before finalize"
+    },
+    {
+      "code": 6008,
+      "name": "unauthorized",
+      "msg": "unauthorized"
+    },
+    {
+      "code": 6009,
+      "name": "invalidTurnTimeout",
+      "msg": "Turn timeout is outside the allowed range"
+    },
+    {
+      "code": 6010,
+      "name": "timeoutNotReached",
+      "msg": "Opponent's turn deadline has not passed yet"
+    },
+    {
+      "code": 6011,
+      "name": "lobbyNotExpired",
+      "msg": "Open game has not reached its lobby expiry yet"
+    },
+    {
+      "code": 6012,
+      "name": "revealWindowOpen",
+      "msg": "Reveal window is still open"
+    },
+    {
+      "code": 6013,
+      "name": "invalidCommitmentMode",
+      "msg": "Unknown board commitment mode"
+    },
+    {
+      "code": 6014,
+      "name": "invalidCellProof",
+      "msg": "Missing or invalid Merkle proof for the shot cell"
+    },
+    {
+      "code": 6015,
+      "name": "invalidShipId",
+      "msg": "Ship id is outside the fleet"
+    },
+    {
+      "code": 6016,
+      "name": "shipAlreadySunk",
+      "msg": "Ship was already declared sunk"
+    },
+    {
+      "code": 6017,
+      "name": "invalidFleet",
+      "msg": "Fleet must have 1–5 ships, each 2–5 cells long"
+    },
+    {
+      "code": 6018,
+      "name": "invalidBoardSize",
+      "msg": "Board must be 5–16 cells per side and at most 128 cells"
+    },
+    {
+      "code": 6019,
+      "name": "invalidGameMode",
+      "msg": "Unknown game mode, or salvo combined with Merkle commitments"
+    },
+    {
+      "code": 6020,
+      "name": "wrongGameMode",
+      "msg": "Instruction does not match this game's mode"
+    },
+    {
+      "code": 6021,
+      "name": "invalidSalvoSize",
+      "msg": "Salvo must have one shot per ship afloat (or per unshot cell left)"
+    },
+    {
+      "code": 6022,
+      "name": "missingTokenAccounts",
+      "msg": "Token game requires the vault, token accounts and token program"
+    },
+    {
+      "code": 6023,
+      "name": "invalidMint",
+      "msg": "Token account does not match the game's mint"
+    },
+    {
+      "code": 6024,
+      "name": "invalidFee",
+      "msg": "Fee exceeds the maximum of 1000 bps"
+    },
+    {
+      "code": 6025,
+      "name": "insufficientTreasury",
+      "msg": "Treasury balance too low for this withdrawal"
+    },
+    {
+      "code": 6026,
+      "name": "invalidBond",
+      "msg": "Both players' wager plus bond overflows"
+    },
+    {
+      "code": 6027,
+      "name": "invalidCommitFrequency",
+      "msg": "Commit frequency outside the configured bounds"
+    },
+    {
+      "code": 6028,
+      "name": "validatorNotAllowed",
+      "msg": "Validator is not on the allowlist"
+    },
+    {
+      "code": 6029,
+      "name": "invalidDelegationPolicy",
+      "msg": "Invalid delegation policy"
+    },
+    {
+      "code": 6030,
+      "name": "invalidSession",
+      "msg": "Session key is not valid for this game"
+    },
+    {
+      "code": 6031,
+      "name": "sessionExpired",
+      "msg": "Session has expired"
+    },
+    {
+      "code": 6032,
+      "name": "invalidSessionLimits",
+      "msg": "Session duration or funding exceeds the limit"
+    },
+    {
+      "code": 6033,
+      "name": "notInvited",
+      "msg": "This game is reserved for an invited opponent"
+    },
+    {
+      "code": 6034,
+      "name": "lobbyFull",
+      "msg": "Lobby page is full"
+    },
+    {
+      "code": 6035,
+      "name": "invalidLobby",
+      "msg": "Lobby page does not match this game"
+    },
+    {
+      "code": 6036,
+      "name": "settlementOverflow",
+      "msg": "Settlement amount overflows"
+    }
+  ],
+  "types": [
+    {
+      "name": "cellProof",
+      "docs": [
+        "Inclusion proof for the shot cell, supplied with `respond_shot` in Merkle mode."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "cellSalt",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "siblings",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "config",
+      "docs": [
+        "Global protocol settings, a singleton PDA created by the program's upgrade authority."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "treasuryAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "minCommitFrequencyMs",
+            "type": "u32"
+          },
+          {
+            "name": "maxCommitFrequencyMs",
+            "type": "u32"
+          },
+          {
+            "name": "allowedValidators",
+            "docs": [
+              "ER validators games may be delegated to; empty allows any."
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "game",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player1",
+            "type": "pubkey"
+          },
+          {
+            "name": "player2",
+            "type": "pubkey"
+          },
+          {
+            "name": "invited",
+            "type": "pubkey"
+          },
+          {
+            "name": "lobby",
+            "type": "pubkey"
+          },
+          {
+            "name": "p1BoardHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "p2BoardHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "p1Shots",
+            "type": "u128"
+          },
+          {
+            "name": "p2Shots",
+            "type": "u128"
+          },
+          {
+            "name": "p1DeclaredHits",
+            "type": "u128"
+          },
+          {
+            "name": "p2DeclaredHits",
+            "type": "u128"
+          },
+          {
+            "name": "p1Board",
+            "type": "u128"
+          },
+          {
+            "name": "p2Board",
+            "type": "u128"
+          },
+          {
+            "name": "pendingShots",
+            "type": "u128"
+          },
+          {
+            "name": "p1SunkCells",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "p2SunkCells",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "fleet",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "wager",
+            "type": "u64"
+          },
+          {
+            "name": "bond",
+            "type": "u64"
+          },
+          {
+            "name": "gameId",
+            "type": "u64"
+          },
+          {
+            "name": "lastActionTs",
+            "type": "i64"
+          },
+          {
+            "name": "turnTimeoutSecs",
+            "type": "i64"
+          },
+          {
+            "name": "revealDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "p1HitsOnP2",
+            "type": "u8"
+          },
+          {
+            "name": "p2HitsOnP1",
+            "type": "u8"
+          },
+          {
+            "name": "p1SunkShips",
+            "type": "u8"
+          },
+          {
+            "name": "p2SunkShips",
+            "type": "u8"
+          },
+          {
+            "name": "fleetSize",
+            "type": "u8"
+          },
+          {
+            "name": "totalShipCells",
+            "type": "u8"
+          },
+          {
+            "name": "boardRows",
+            "type": "u8"
+          },
+          {
+            "name": "boardCols",
+            "type": "u8"
+          },
+          {
+            "name": "lastShotCell",
+            "type": "u8"
+          },
+          {
+            "name": "turnState",
+            "type": "u8"
+          },
+          {
+            "name": "gameState",
+            "type": "u8"
+          },
+          {
+            "name": "commitmentMode",
+            "type": "u8"
+          },
+          {
+            "name": "gameMode",
+            "type": "u8"
+          },
+          {
+            "name": "rated",
+            "type": "bool"
+          },
+          {
+            "name": "p1Revealed",
+            "type": "bool"
+          },
+          {
+            "name": "p2Revealed",
+            "type": "bool"
+          },
+          {
+            "name": "p1Conceded",
+            "type": "bool"
+          },
+          {
+            "name": "p2Conceded",
+            "type": "bool"
+          },
+          {
+            "name": "p1Cheated",
+            "type": "bool"
+          },
+          {
+            "name": "p2Cheated",
+            "type": "bool"
+          },
+          {
+            "name": "winner",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "gameNotReady",
-      "msg": "Both players must reveal before finalize"
+      "name": "gameRules",
+      "docs": [
+        "Ruleset chosen by player1 at `create_game` and fixed for the rest of the game."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "turnTimeoutSecs",
+            "type": "i64"
+          },
+          {
+            "name": "commitmentMode",
+            "type": "u8"
+          },
+          {
+            "name": "fleet",
+            "docs": [
+              "Ship lengths, one entry per ship (1–5 ships of 2–5 cells)."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "boardRows",
+            "type": "u8"
+          },
+          {
+            "name": "boardCols",
+            "type": "u8"
+          },
+          {
+            "name": "gameMode",
+            "type": "u8"
+          },
+          {
+            "name": "bond",
+            "docs": [
+              "Honesty bond each player posts on top of the wager; 0 for none."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rated",
+            "docs": [
+              "Whether settlement updates the players' Elo ratings."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "unauthorized",
-      "msg": "unauthorized"
-    }
-  ],
-  "types": [
-    {
-      "name": "game",
+      "name": "lobbyEntry",
+      "docs": [
+        "Open game as listed in a lobby page, with enough of its ruleset to filter on."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "player1",
+            "name": "game",
             "type": "pubkey"
           },
           {
-            "name": "player2",
+            "name": "mint",
             "type": "pubkey"
           },
           {
-            "name": "p1BoardHash",
+            "name": "wager",
+            "type": "u64"
+          },
+          {
+            "name": "gameMode",
+            "type": "u8"
+          },
+          {
+            "name": "commitmentMode",
+            "type": "u8"
+          },
+          {
+            "name": "boardRows",
+            "type": "u8"
+          },
+          {
+            "name": "boardCols",
+            "type": "u8"
+          },
+          {
+            "name": "rated",
+            "type": "bool"
+          },
+          {
+            "name": "bond",
+            "type": "u64"
+          },
+          {
+            "name": "fleet",
             "type": {
               "array": [
                 "u8",
-                32
+                5
               ]
             }
           },
           {
-            "name": "p2BoardHash",
+            "name": "fleetSize",
+            "type": "u8"
+          },
+          {
+            "name": "turnTimeoutSecs",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lobbyPage",
+      "docs": [
+        "One page of open games in a wager bracket. Pages are PDAs",
+        "`[\"lobby\", bracket, page_le]`, so clients can walk a bracket page by page."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bracket",
+            "type": "u8"
+          },
+          {
+            "name": "page",
+            "type": "u16"
+          },
+          {
+            "name": "entries",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "vec": {
+                "defined": {
+                  "name": "lobbyEntry"
+                }
+              }
             }
           },
           {
-            "name": "p1Shots",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerProfile",
+      "docs": [
+        "Per-wallet profile, created on a player's first `create_game` or settled game. Its",
+        "counter assigns game ids, so a player's games are the PDAs for ids `0..games_created`;",
+        "the rest is a lifetime record updated at settlement."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
           },
           {
-            "name": "p2Shots",
+            "name": "gamesCreated",
             "type": "u64"
           },
           {
-            "name": "p1DeclaredHits",
-            "type": "u64"
+            "name": "gamesPlayed",
+            "type": "u32"
           },
           {
-            "name": "p2DeclaredHits",
-            "type": "u64"
+            "name": "wins",
+            "type": "u32"
           },
           {
-            "name": "p1Board",
-            "type": "u64"
+            "name": "losses",
+            "type": "u32"
           },
           {
-            "name": "p2Board",
-            "type": "u64"
+            "name": "draws",
+            "type": "u32"
           },
           {
-            "name": "wager",
-            "type": "u64"
+            "name": "cheatsDetected",
+            "type": "u32"
           },
           {
-            "name": "gameId",
+            "name": "totalShots",
+            "type": "u32"
+          },
+          {
+            "name": "totalHits",
+            "type": "u32"
+          },
+          {
+            "name": "lamportsWon",
             "type": "u64"
           },
           {
-            "name": "p1HitsOnP2",
-            "type": "u8"
+            "name": "lamportsLost",
+            "type": "u64"
           },
           {
-            "name": "p2HitsOnP1",
-            "type": "u8"
+            "name": "rating",
+            "type": "u32"
           },
           {
-            "name": "lastShotCell",
+            "name": "bump",
             "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sessionToken",
+      "docs": [
+        "Lets a short-lived ephemeral key sign ER moves for one player in one game."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
           },
           {
-            "name": "turnState",
-            "type": "u8"
+            "name": "game",
+            "type": "pubkey"
           },
           {
-            "name": "gameState",
-            "type": "u8"
+            "name": "sessionKey",
+            "type": "pubkey"
           },
           {
-            "name": "p1Revealed",
-            "type": "bool"
+            "name": "expiresAt",
+            "type": "i64"
           },
           {
-            "name": "p2Revealed",
-            "type": "bool"
+            "name": "lamportsFunded",
+            "type": "u64"
           },
           {
-            "name": "winner",
+            "name": "bump",
             "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "shotResult",
+      "docs": [
+        "Responder's answer to the last shot. `ship_id` indexes the game's fleet."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "miss"
           },
+          {
+            "name": "hit"
+          },
+          {
+            "name": "sunk",
+            "fields": [
+              {
+                "name": "shipId",
+                "type": "u8"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "treasury",
+      "docs": [
+        "Program-owned fee sink. Lamport fees accrue on this account; token fees go to token",
+        "accounts whose owner is this PDA."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
             "name": "bump",
             "type": "u8"
@@ -36,8 +36,25 @@ export const MAGIC_ROUTER_RPC = "https://devnet-router.magicblock.app";
 export const MAGIC_ROUTER_WS = "wss://devnet-router.magicblock.app";
 
 export const GAME_SEED = "game";
+export const PROFILE_SEED = "profile";
 export const BUFFER_SEED = "buffer";
 
+// Board this app creates games with (the program accepts up to 128 cells)
+export const BOARD_ROWS = 8;
+export const BOARD_COLS = 8;
+export const BOARD_CELLS = BOARD_ROWS * BOARD_COLS;
+
+// Ship lengths of the fleet this app creates games with. Placement builds exactly these ships
+// and the program rejects any revealed board that is not this fleet, so it is defined only here.
+export const FLEET = [2, 3, 4];
+export const SHIP_CELLS = FLEET.reduce((total, len) => total + len, 0);
+
+// How often the ER commits the delegated game back to L1 (bounded by the program config)
+export const COMMIT_FREQUENCY_MS = 5_000;
+
+// Seconds the player on move has before the opponent can claim_timeout
+export const TURN_TIMEOUT_SECS = 300;
+
 // Game state enum values - MUST match on-chain state.rs
 export enum GameState {
   WaitingForPlayer = 0,
@@ -45,6 +62,16 @@ export enum GameState {
   Active = 2,
   WaitingReveal = 3,
   Finished = 4,
+  Drawn = 5,
+  Voided = 6,
+}
+
+export function isSettled(gameState: number): boolean {
+  return (
+    gameState === GameState.Finished ||
+    gameState === GameState.Drawn ||
+    gameState === GameState.Voided
+  );
 }
 
 // Turn state enum values
@@ -58,23 +85,47 @@ export enum TurnState {
 export interface GameAccount {
   player1: PublicKey;
   player2: PublicKey;
+  invited: PublicKey;
+  lobby: PublicKey;
   p1BoardHash: number[];
   p2BoardHash: number[];
+  mint: PublicKey;
   p1Shots: BN;
   p2Shots: BN;
   p1DeclaredHits: BN;
   p2DeclaredHits: BN;
   p1Board: BN;
   p2Board: BN;
+  pendingShots: BN;
+  p1SunkCells: number[];
+  p2SunkCells: number[];
+  fleet: number[];
   wager: BN;
+  bond: BN;
   gameId: BN;
+  lastActionTs: BN;
+  turnTimeoutSecs: BN;
+  revealDeadline: BN;
   p1HitsOnP2: number;
   p2HitsOnP1: number;
+  p1SunkShips: number;
+  p2SunkShips: number;
+  fleetSize: number;
+  totalShipCells: number;
+  boardRows: number;
+  boardCols: number;
   lastShotCell: number;
   turnState: number;
   gameState: number;
+  commitmentMode: number;
+  gameMode: number;
+  rated: boolean;
   p1Revealed: boolean;
   p2Revealed: boolean;
+  p1Conceded: boolean;
+  p2Conceded: boolean;
+  p1Cheated: boolean;
+  p2Cheated: boolean;
   winner: number;
   bump: number;
 }
@@ -90,6 +141,13 @@ export function getGamePda(player1: PublicKey, gameId: BN): [PublicKey, number]
   );
 }
 
+export function getProfilePda(player: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PROFILE_SEED), player.toBuffer()],
+    FLEET_WARS_PROGRAM_ID
+  );
+}
+
 export function getBufferPda(gamePda: PublicKey): [PublicKey, number] {
   // Buffer PDA is derived with: ["buffer", delegated_account]
   // Uses the BUFFER_PROGRAM_ID (not Fleet Wars or Delegation program)
@@ -113,38 +171,39 @@ export function getDelegationMetadataPda(gamePda: PublicKey): [PublicKey, number
   );
 }
 
-// Compute board hash: sha256(board_bits || salt)
+// Compute board hash: sha256(board_bits as u128 LE || salt)
 export async function computeBoardHash(
   boardBits: bigint,
   salt: Uint8Array
 ): Promise<Uint8Array> {
-  const boardBuffer = new ArrayBuffer(8);
+  const boardBuffer = new ArrayBuffer(16);
   const view = new DataView(boardBuffer);
-  view.setBigUint64(0, boardBits, true); // little endian
+  view.setBigUint64(0, boardBits & BigInt("0xffffffffffffffff"), true); // little endian
+  view.setBigUint64(8, boardBits >> BigInt(64), true);
 
-  const combined = new Uint8Array(8 + 32);
+  const combined = new Uint8Array(16 + 32);
   combined.set(new Uint8Array(boardBuffer), 0);
-  combined.set(salt, 8);
+  combined.set(salt, 16);
 
   const hashBuffer = await crypto.subtle.digest("SHA-256", combined);
   return new Uint8Array(hashBuffer);
 }
 
-// Convert ship positions (array of cell indices) to a u64 bitmask
+// Convert ship positions (array of cell indices) to a u128 bitmask
 export function shipsToBitmask(ships: number[]): bigint {
   let bitmask = BigInt(0);
   for (const cell of ships) {
-    if (cell >= 0 && cell < 64) {
+    if (cell >= 0 && cell < 128) {
       bitmask |= BigInt(1) << BigInt(cell);
     }
   }
   return bitmask;
 }
 
-// Convert u64 bitmask to array of cell indices
+// Convert u128 bitmask to array of cell indices
 export function bitmaskToShips(bitmask: bigint): number[] {
   const ships: number[] = [];
-  for (let i = 0; i < 64; i++) {
+  for (let i = 0; i < 128; i++) {
     if ((bitmask & (BigInt(1) << BigInt(i))) !== BigInt(0)) {
       ships.push(i);
     }
@@ -152,6 +211,65 @@ export function bitmaskToShips(bitmask: bigint): number[] {
   return ships;
 }
 
+// Split ship cells into straight ships matching `fleet`, indexed by ship id (same search as
+// the program's fleet check). Returns null if the cells are not a legal fleet.
+export function decomposeFleet(
+  ships: number[],
+  rows: number,
+  cols: number,
+  fleet: number[]
+): number[][] | null {
+  const remaining = new Set(ships);
+  const placed: number[][] = fleet.map(() => []);
+
+  const search = (used: boolean[]): boolean => {
+    if (remaining.size === 0) return used.every(Boolean);
+    const start = Math.min(...remaining);
+    const [row, col] = [Math.floor(start / cols), start % cols];
+
+    for (let id = 0; id < fleet.length; id++) {
+      if (used[id]) continue;
+      const len = fleet[id];
+      const orientations: number[][] = [];
+      if (col + len <= cols) orientations.push([...Array(len).keys()].map((k) => start + k));
+      if (len > 1 && row + len <= rows) {
+        orientations.push([...Array(len).keys()].map((k) => start + k * cols));
+      }
+
+      for (const cells of orientations) {
+        if (!cells.every((c) => remaining.has(c))) continue;
+        cells.forEach((c) => remaining.delete(c));
+        placed[id] = cells;
+        const next = [...used];
+        next[id] = true;
+        if (search(next)) return true;
+        cells.forEach((c) => remaining.add(c));
+      }
+    }
+    return false;
+  };
+
+  return search(fleet.map(() => false)) ? placed : null;
+}
+
+type Unit = Record<string, never>;
+export type ShotResult = { miss: Unit } | { hit: Unit } | { sunk: { shipId: number } };
+
+// Answer to the opponent's last shot. A hit that completes a ship must be declared sunk,
+// otherwise finalize treats the responder as a cheater.
+export function shotResult(ships: number[], game: GameAccount, hit: boolean): ShotResult {
+  if (!hit) return { miss: {} };
+
+  const fleet = game.fleet.slice(0, game.fleetSize);
+  const placed = decomposeFleet(ships, game.boardRows, game.boardCols, fleet);
+  const shipId = placed?.findIndex((cells) => cells.includes(game.lastShotCell)) ?? -1;
+  if (!placed || shipId < 0) return { hit: {} };
+
+  const opponentShots = game.turnState === TurnState.P1Responds ? game.p2Shots : game.p1Shots;
+  const sunk = placed[shipId].every((cell) => wasCellShot(opponentShots, cell));
+  return sunk ? { sunk: { shipId } } : { hit: {} };
+}
+
 // Generate random 32-byte salt
 export function generateSalt(): Uint8Array {
   return crypto.getRandomValues(new Uint8Array(32));
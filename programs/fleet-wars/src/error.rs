@@ -8,16 +8,72 @@ pub enum FleetWarsError {
     NotYourTurn,
     #[msg("Cell already shot")]
     CellAlreadyShot,
-    #[msg("Invalid cell — out of bounds (0–63)")]
+    #[msg("Invalid cell — outside the game board")]
     InvalidCell,
     #[msg("Game is not active")]
     GameNotActive,
     #[msg("Board hash mismatch — cheater detected")]
     BoardHashMismatch,
-    #[msg("Invalid board: must have exactly 9 ship cells")]
+    #[msg("Invalid board: ships must be straight, non-overlapping and match the fleet")]
     InvalidBoard,
     #[msg("Both players must reveal before finalize")]
     GameNotReady,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Turn timeout is outside the allowed range")]
+    InvalidTurnTimeout,
+    #[msg("Opponent's turn deadline has not passed yet")]
+    TimeoutNotReached,
+    #[msg("Open game has not reached its lobby expiry yet")]
+    LobbyNotExpired,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Unknown board commitment mode")]
+    InvalidCommitmentMode,
+    #[msg("Missing or invalid Merkle proof for the shot cell")]
+    InvalidCellProof,
+    #[msg("Ship id is outside the fleet")]
+    InvalidShipId,
+    #[msg("Ship was already declared sunk")]
+    ShipAlreadySunk,
+    #[msg("Fleet must have 1–5 ships, each 2–5 cells long")]
+    InvalidFleet,
+    #[msg("Board must be 5–16 cells per side and at most 128 cells")]
+    InvalidBoardSize,
+    #[msg("Unknown game mode, or salvo combined with Merkle commitments")]
+    InvalidGameMode,
+    #[msg("Instruction does not match this game's mode")]
+    WrongGameMode,
+    #[msg("Salvo must have one shot per ship afloat (or per unshot cell left)")]
+    InvalidSalvoSize,
+    #[msg("Token game requires the vault, token accounts and token program")]
+    MissingTokenAccounts,
+    #[msg("Token account does not match the game's mint")]
+    InvalidMint,
+    #[msg("Fee exceeds the maximum of 1000 bps")]
+    InvalidFee,
+    #[msg("Treasury balance too low for this withdrawal")]
+    InsufficientTreasury,
+    #[msg("Both players' wager plus bond overflows")]
+    InvalidBond,
+    #[msg("Commit frequency outside the configured bounds")]
+    InvalidCommitFrequency,
+    #[msg("Validator is not on the allowlist")]
+    ValidatorNotAllowed,
+    #[msg("Invalid delegation policy")]
+    InvalidDelegationPolicy,
+    #[msg("Session key is not valid for this game")]
+    InvalidSession,
+    #[msg("Session has expired")]
+    SessionExpired,
+    #[msg("Session duration or funding exceeds the limit")]
+    InvalidSessionLimits,
+    #[msg("This game is reserved for an invited opponent")]
+    NotInvited,
+    #[msg("Lobby page is full")]
+    LobbyFull,
+    #[msg("Lobby page does not match this game")]
+    InvalidLobby,
+    #[msg("Settlement amount overflows")]
+    SettlementOverflow,
 }
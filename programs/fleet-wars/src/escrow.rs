@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::{Game, GAME_SEED};
+use crate::error::FleetWarsError;
+
+/// Stakes live either as lamports on the Game PDA itself (`game.mint == default`) or as
+/// tokens in the game's vault, a PDA token account whose authority is the Game PDA.
+pub fn is_token_game(game: &Game) -> bool {
+    game.mint != Pubkey::default()
+}
+
+/// Moves `amount` from a player into the game's escrow.
+pub fn deposit<'info>(
+    game: &Account<'info, Game>,
+    player: &Signer<'info>,
+    player_token: Option<&Account<'info, TokenAccount>>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if !is_token_game(game) {
+        return system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: player.to_account_info(),
+                    to: game.to_account_info(),
+                },
+            ),
+            amount,
+        );
+    }
+
+    let (vault, token_program) = token_accounts(vault, token_program)?;
+    let player_token = player_token.ok_or(FleetWarsError::MissingTokenAccounts)?;
+    require!(player_token.mint == game.mint, FleetWarsError::InvalidMint);
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: player_token.to_account_info(),
+                to: vault.to_account_info(),
+                authority: player.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Pays `amount` out of the game's escrow to `recipient`. For lamport games `to` must be
+/// the recipient's wallet; for token games it must be a token account of the game's mint
+/// owned by the recipient.
+pub fn release<'info>(
+    game: &Account<'info, Game>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    to: &AccountInfo<'info>,
+    recipient: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if !is_token_game(game) {
+        require!(to.key() == recipient, FleetWarsError::Unauthorized);
+        **game.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **to.try_borrow_mut_lamports()? += amount;
+        return Ok(());
+    }
+
+    let (vault, token_program) = token_accounts(vault, token_program)?;
+    let destination = TokenAccount::try_deserialize(&mut &to.try_borrow_data()?[..])?;
+    require!(destination.owner == recipient, FleetWarsError::Unauthorized);
+    require!(destination.mint == game.mint, FleetWarsError::InvalidMint);
+
+    let game_id = game.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, game.player1.as_ref(), &game_id, &[game.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: to.clone(),
+                authority: game.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+/// Refunds player1's stake from a game nobody joined. Lamport stakes leave with the Game
+/// account close; token stakes are paid back from the vault, which is then closed to
+/// player1 as well.
+pub fn refund_creator<'info>(
+    game: &Account<'info, Game>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    player1_token: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    player1: &AccountInfo<'info>,
+) -> Result<()> {
+    if !is_token_game(game) {
+        return Ok(());
+    }
+
+    let refund_to = player1_token
+        .ok_or(FleetWarsError::MissingTokenAccounts)?
+        .to_account_info();
+    release(game, vault, token_program, &refund_to, game.player1, game.stake())?;
+    close_vault(game, vault, token_program, player1)
+}
+
+/// Closes an emptied token vault, returning its rent to `destination`. No-op for
+/// lamport games.
+pub fn close_vault<'info>(
+    game: &Account<'info, Game>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if !is_token_game(game) {
+        return Ok(());
+    }
+
+    let (vault, token_program) = token_accounts(vault, token_program)?;
+    let game_id = game.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, game.player1.as_ref(), &game_id, &[game.bump]];
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination: destination.clone(),
+            authority: game.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
+
+fn token_accounts<'a, 'info>(
+    vault: Option<&'a Account<'info, TokenAccount>>,
+    token_program: Option<&'a Program<'info, Token>>,
+) -> Result<(&'a Account<'info, TokenAccount>, &'a Program<'info, Token>)> {
+    match (vault, token_program) {
+        (Some(vault), Some(token_program)) => Ok((vault, token_program)),
+        _ => Err(FleetWarsError::MissingTokenAccounts.into()),
+    }
+}
@@ -0,0 +1,149 @@
+/// True if `board` decomposes into straight, non-overlapping ships whose lengths are
+/// exactly `lengths` (each used once) on a `rows` x `cols` grid. Ships may not wrap
+/// across rows and no cell outside the grid may be occupied.
+///
+/// The decomposition must also agree with the owner's sunk declarations: a ship declared
+/// sunk at `sunk_cells[id]` must contain that cell and be fully covered by
+/// `opponent_shots`, and a ship never declared sunk must not be.
+pub fn matches_sunk_declarations(
+    board: u128,
+    rows: u8,
+    cols: u8,
+    lengths: &[u8],
+    opponent_shots: u128,
+    sunk_ships: u8,
+    sunk_cells: &[u8],
+) -> bool {
+    let accept = |ship: usize, mask: u128| {
+        let fully_hit = opponent_shots & mask == mask;
+        if sunk_ships & (1 << ship) != 0 {
+            fully_hit && mask & (1u128 << sunk_cells[ship]) != 0
+        } else {
+            !fully_hit
+        }
+    };
+    let search = Search { rows: rows as u32, cols: cols as u32, lengths, accept: &accept };
+    search.fits(board)
+}
+
+struct Search<'a> {
+    rows: u32,
+    cols: u32,
+    lengths: &'a [u8],
+    accept: &'a dyn Fn(usize, u128) -> bool,
+}
+
+impl Search<'_> {
+    fn fits(&self, board: u128) -> bool {
+        let cells = self.rows * self.cols;
+        if cells < u128::BITS && board >> cells != 0 {
+            return false;
+        }
+        if self.lengths.iter().map(|&l| l as u32).sum::<u32>() != board.count_ones() {
+            return false;
+        }
+        self.place_ships(board, 0)
+    }
+
+    /// Backtracking search. The lowest occupied cell must be the first cell (left end or
+    /// top end) of some ship, so only two orientations per remaining ship are tried.
+    fn place_ships(&self, remaining: u128, used: u32) -> bool {
+        if remaining == 0 {
+            return used.count_ones() as usize == self.lengths.len();
+        }
+
+        let start = remaining.trailing_zeros();
+        let (row, col) = (start / self.cols, start % self.cols);
+
+        for (i, &len) in self.lengths.iter().enumerate() {
+            if used & (1 << i) != 0 {
+                continue;
+            }
+
+            let len = len as u32;
+            let mut orientations = [None, None];
+            if col + len <= self.cols {
+                orientations[0] = Some(ship_mask(start, len, 1));
+            }
+            if len > 1 && row + len <= self.rows {
+                orientations[1] = Some(ship_mask(start, len, self.cols));
+            }
+
+            for ship in orientations.into_iter().flatten() {
+                if remaining & ship == ship
+                    && (self.accept)(i, ship)
+                    && self.place_ships(remaining & !ship, used | (1 << i))
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+fn ship_mask(start: u32, len: u32, step: u32) -> u128 {
+    (0..len).fold(0u128, |mask, k| mask | (1u128 << (start + k * step)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(cells: &[u32]) -> u128 {
+        cells.iter().fold(0, |b, &c| b | (1u128 << c))
+    }
+
+    fn valid(cells: &[u32], rows: u8, cols: u8, lengths: &[u8]) -> bool {
+        matches_sunk_declarations(board(cells), rows, cols, lengths, 0, 0, &[0; 5])
+    }
+
+    #[test]
+    fn accepts_straight_fleet() {
+        assert!(valid(&[0, 1, 20, 30, 40], 10, 10, &[2, 3]));
+        assert!(valid(&[20, 30, 40, 0, 1], 10, 10, &[3, 2]));
+    }
+
+    #[test]
+    fn rejects_ship_wrapping_across_a_row() {
+        assert!(!valid(&[8, 9, 10], 10, 10, &[3]));
+    }
+
+    #[test]
+    fn rejects_overlapping_ships() {
+        // A cross: the two ships would have to share the centre cell.
+        assert!(!valid(&[1, 10, 11, 12, 21], 10, 10, &[2, 3]));
+    }
+
+    #[test]
+    fn rejects_cells_outside_the_grid() {
+        assert!(valid(&[0, 1], 5, 5, &[2]));
+        assert!(!valid(&[0, 30], 5, 5, &[2]));
+        assert!(!valid(&[23, 24, 25], 5, 5, &[3]));
+    }
+
+    #[test]
+    fn uses_each_duplicate_length_once() {
+        assert!(valid(&[0, 1, 2, 20, 21, 22], 10, 10, &[3, 3]));
+        assert!(!valid(&[0, 1, 20, 21, 22, 23], 10, 10, &[3, 3]));
+    }
+
+    #[test]
+    fn rejects_sunk_cell_outside_the_ship() {
+        let fleet = board(&[0, 1, 20, 21, 22]);
+        let shots = board(&[0, 1]);
+        let mut sunk_cells = [0u8; 5];
+
+        sunk_cells[0] = 1;
+        assert!(matches_sunk_declarations(fleet, 10, 10, &[2, 3], shots, 0b01, &sunk_cells));
+        sunk_cells[0] = 20;
+        assert!(!matches_sunk_declarations(fleet, 10, 10, &[2, 3], shots, 0b01, &sunk_cells));
+    }
+
+    #[test]
+    fn rejects_fully_hit_ship_never_declared_sunk() {
+        let fleet = board(&[0, 1, 20, 21, 22]);
+        let shots = board(&[0, 1, 20]);
+        assert!(!matches_sunk_declarations(fleet, 10, 10, &[2, 3], shots, 0, &[0; 5]));
+    }
+}
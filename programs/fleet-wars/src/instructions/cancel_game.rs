@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::lobby;
+use crate::state::{Game, LobbyPage, game_state, VAULT_SEED};
+use crate::error::FleetWarsError;
+
+/// Creator backs out of a game nobody has joined. The escrowed wager, bond and all rent
+/// go back to player1.
+pub fn handler(ctx: Context<CancelGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+
+    require!(
+        game.game_state == game_state::WAITING_FOR_PLAYER,
+        FleetWarsError::InvalidGameState
+    );
+
+    lobby::unregister(ctx.accounts.lobby.as_mut(), game.key(), game)?;
+
+    escrow::refund_creator(
+        game,
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.player1_token.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.player1.to_account_info(),
+    )?;
+
+    msg!("Game {} cancelled by creator, stake refunded", game.game_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(mut, has_one = player1 @ FleetWarsError::Unauthorized, close = player1)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player1: Signer<'info>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player1_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// Optional — the lobby page listing this game, required when `game.lobby` is set
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+}
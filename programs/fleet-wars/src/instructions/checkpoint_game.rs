@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::ephem::commit_accounts;
+use crate::state::{Game, game_state};
+use crate::error::FleetWarsError;
+
+/// Called from the ER by either player to push the current game state to L1 without
+/// ending the delegation, so play continues uninterrupted.
+pub fn handler(ctx: Context<CheckpointGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+
+    require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
+    require!(
+        ctx.accounts.player.key() == game.player1 || ctx.accounts.player.key() == game.player2,
+        FleetWarsError::Unauthorized
+    );
+
+    commit_accounts(
+        &ctx.accounts.player.to_account_info(),
+        vec![&game.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+
+    msg!("Game {} checkpointed to base layer", game.game_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CheckpointGame<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// CHECK: MagicBlock magic context
+    #[account(mut, address = ephemeral_rollups_sdk::consts::MAGIC_CONTEXT_ID)]
+    pub magic_context: AccountInfo<'info>,
+    /// CHECK: MagicBlock magic program
+    #[account(address = ephemeral_rollups_sdk::consts::MAGIC_PROGRAM_ID)]
+    pub magic_program: AccountInfo<'info>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+use crate::state::{Game, game_state};
+use crate::error::FleetWarsError;
+
+/// Called from the ER by the waiting player once the opponent has sat on their move
+/// for longer than `turn_timeout_secs`. The claimant is declared winner and the game
+/// is committed and undelegated back to L1.
+pub fn handler(ctx: Context<ClaimTimeout>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
+
+    // The player on move is the one who owes the next fire/respond.
+    let (claimant, winner) = match game.player_on_move() {
+        Some(1) => (game.player2, 2u8),
+        Some(_) => (game.player1, 1u8),
+        None => return Err(FleetWarsError::InvalidGameState.into()),
+    };
+    require!(ctx.accounts.player.key() == claimant, FleetWarsError::Unauthorized);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(game.turn_timed_out(now), FleetWarsError::TimeoutNotReached);
+
+    game.end_battle(winner, now);
+
+    msg!("Turn timeout claimed — winner declared as P{}, pending reveal", winner);
+
+    game.exit(&crate::ID)?;
+    commit_and_undelegate_accounts(
+        &ctx.accounts.player.to_account_info(),
+        vec![&game.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimTimeout<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// CHECK: MagicBlock magic context
+    #[account(mut, address = ephemeral_rollups_sdk::consts::MAGIC_CONTEXT_ID)]
+    pub magic_context: AccountInfo<'info>,
+    /// CHECK: MagicBlock magic program
+    #[account(address = ephemeral_rollups_sdk::consts::MAGIC_PROGRAM_ID)]
+    pub magic_program: AccountInfo<'info>,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::settlement;
+use crate::state::{
+    Config, Game, PlayerProfile, Treasury, game_state, CONFIG_SEED, PROFILE_SEED, TREASURY_SEED,
+    VAULT_SEED,
+};
+use crate::error::FleetWarsError;
+
+/// Once the reveal window has closed, every player who has not revealed is recorded as a
+/// cheater. A lone revealer whose board passes the finalize checks takes the whole pot and
+/// both bonds; if their board also fails, or nobody revealed, the game is voided and both
+/// stakes are slashed to the treasury.
+pub fn handler(ctx: Context<ClaimUnrevealedForfeit>) -> Result<()> {
+    {
+        let game = &mut ctx.accounts.game;
+
+        require!(
+            game.game_state == game_state::WAITING_REVEAL,
+            FleetWarsError::InvalidGameState
+        );
+        // Once each player has revealed or conceded the game settles through finalize.
+        let p1_done = game.p1_revealed || game.p1_conceded;
+        let p2_done = game.p2_revealed || game.p2_conceded;
+        require!(!(p1_done && p2_done), FleetWarsError::InvalidGameState);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now > game.reveal_deadline, FleetWarsError::RevealWindowOpen);
+
+        // The lone revealer's board is held to the same checks as at finalize.
+        let (p1_cheated, p2_cheated) = (settlement::cheated(game, 1), settlement::cheated(game, 2));
+        settlement::decide(game, p1_cheated, p2_cheated);
+    }
+
+    let game = &ctx.accounts.game;
+    let vault = ctx.accounts.vault.as_ref();
+    let token_program = ctx.accounts.token_program.as_ref();
+    let treasury_to = match &ctx.accounts.treasury_token {
+        Some(treasury_token) => treasury_token.clone(),
+        None => ctx.accounts.treasury.to_account_info(),
+    };
+    let treasury_key = ctx.accounts.treasury.key();
+
+    // An honest revealer also takes the non-revealer's bond along with their own; the
+    // protocol fee goes to the treasury PDA (or its token account).
+    let (p1_paid, p2_paid, treasury_paid) = settlement::split(game, &ctx.accounts.config)?;
+    let p1_to = &ctx.accounts.p1_payout;
+    let p2_to = &ctx.accounts.p2_payout;
+    escrow::release(game, vault, token_program, p1_to, game.player1, p1_paid)?;
+    escrow::release(game, vault, token_program, p2_to, game.player2, p2_paid)?;
+    escrow::release(game, vault, token_program, &treasury_to, treasury_key, treasury_paid)?;
+
+    PlayerProfile::record_settlement(
+        (&mut ctx.accounts.p1_profile, ctx.bumps.p1_profile),
+        (&mut ctx.accounts.p2_profile, ctx.bumps.p2_profile),
+        game,
+        p1_paid,
+        p2_paid,
+    );
+
+    msg!(
+        "Game {} forfeited by non-revealer. State: {}, winner: P{}",
+        game.game_id,
+        game.game_state,
+        game.winner
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimUnrevealedForfeit<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    /// CHECK: Player1's wallet, or their token account for token games; checked in escrow::release
+    #[account(mut)]
+    pub p1_payout: AccountInfo<'info>,
+    /// CHECK: Player2's wallet, or their token account for token games; checked in escrow::release
+    #[account(mut)]
+    pub p2_payout: AccountInfo<'info>,
+    /// Pays for any player profile created here
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, game.player1.as_ref()],
+        bump
+    )]
+    pub p1_profile: Account<'info, PlayerProfile>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, game.player2.as_ref()],
+        bump
+    )]
+    pub p2_profile: Account<'info, PlayerProfile>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: Treasury-owned token account receiving the fee in token games; checked in escrow::release
+    #[account(mut)]
+    pub treasury_token: Option<AccountInfo<'info>>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::state::{Game, VAULT_SEED};
+use crate::error::FleetWarsError;
+
+/// Closes a settled game and returns its rent to player1. Either player may call it
+/// once they no longer need the on-chain record.
+pub fn handler(ctx: Context<CloseGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+
+    require!(game.is_settled(), FleetWarsError::InvalidGameState);
+    require!(
+        ctx.accounts.caller.key() == game.player1 || ctx.accounts.caller.key() == game.player2,
+        FleetWarsError::Unauthorized
+    );
+
+    escrow::close_vault(
+        game,
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.player1,
+    )?;
+
+    msg!("Game {} closed, rent returned to {}", game.game_id, game.player1);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(mut, has_one = player1 @ FleetWarsError::Unauthorized, close = player1)]
+    pub game: Account<'info, Game>,
+    /// CHECK: Creator receives the rent; verified against game.player1
+    #[account(mut)]
+    pub player1: AccountInfo<'info>,
+    pub caller: Signer<'info>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
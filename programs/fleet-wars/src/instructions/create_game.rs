@@ -1,66 +1,203 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use crate::state::{Game, game_state, turn_state, GAME_SEED};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::escrow;
+use crate::lobby;
+use crate::state::{
+    Game, GameRules, LobbyPage, PlayerProfile, commitment_mode, game_mode, game_state, turn_state,
+    GAME_SEED, PROFILE_SEED, VAULT_SEED, MAX_BOARD_CELLS, MAX_BOARD_DIM, MAX_SHIPS, MAX_SHIP_LEN,
+    MAX_TURN_TIMEOUT_SECS, MIN_BOARD_DIM, MIN_SHIP_LEN, MIN_TURN_TIMEOUT_SECS,
+};
+use crate::error::FleetWarsError;
 
 pub fn handler(
     ctx: Context<CreateGame>,
-    game_id: u64,
     board_hash: [u8; 32],
     wager: u64,
+    rules: GameRules,
+    invited: Option<Pubkey>,
 ) -> Result<()> {
+    let GameRules {
+        turn_timeout_secs,
+        commitment_mode,
+        fleet,
+        board_rows,
+        board_cols,
+        game_mode,
+        bond,
+        rated,
+    } = rules;
+
+    require!(
+        (MIN_TURN_TIMEOUT_SECS..=MAX_TURN_TIMEOUT_SECS).contains(&turn_timeout_secs),
+        FleetWarsError::InvalidTurnTimeout
+    );
+    require!(
+        commitment_mode == commitment_mode::HASH || commitment_mode == commitment_mode::MERKLE,
+        FleetWarsError::InvalidCommitmentMode
+    );
+    require!(
+        !fleet.is_empty()
+            && fleet.len() <= MAX_SHIPS
+            && fleet.iter().all(|len| (MIN_SHIP_LEN..=MAX_SHIP_LEN).contains(len)),
+        FleetWarsError::InvalidFleet
+    );
+    require!(
+        (MIN_BOARD_DIM..=MAX_BOARD_DIM).contains(&board_rows)
+            && (MIN_BOARD_DIM..=MAX_BOARD_DIM).contains(&board_cols)
+            && board_rows as u16 * board_cols as u16 <= MAX_BOARD_CELLS,
+        FleetWarsError::InvalidBoardSize
+    );
+    // Every ship must fit in at least one orientation, and the fleet must fit on the board.
+    require!(
+        fleet.iter().all(|&len| len <= board_rows.max(board_cols))
+            && fleet.iter().map(|&len| len as u16).sum::<u16>()
+                <= board_rows as u16 * board_cols as u16,
+        FleetWarsError::InvalidFleet
+    );
+
+    require!(
+        game_mode == game_mode::CLASSIC || game_mode == game_mode::SALVO,
+        FleetWarsError::InvalidGameMode
+    );
+    // A salvo answer would need one Merkle proof per shot, which does not fit in a transaction.
+    require!(
+        !(game_mode == game_mode::SALVO && commitment_mode == commitment_mode::MERKLE),
+        FleetWarsError::InvalidGameMode
+    );
+
+    // Settlement moves up to both stakes at once, so twice the stake must fit too.
+    let stake = wager.checked_add(bond).ok_or(FleetWarsError::InvalidBond)?;
+    stake.checked_mul(2).ok_or(FleetWarsError::InvalidBond)?;
+
+    let mut fleet_lengths = [0u8; MAX_SHIPS];
+    fleet_lengths[..fleet.len()].copy_from_slice(&fleet);
+
+    // Token wagers need the whole set of token accounts; lamport games pass none of them.
+    let mint = match &ctx.accounts.mint {
+        Some(mint) => {
+            require!(
+                ctx.accounts.vault.is_some()
+                    && ctx.accounts.player1_token.is_some()
+                    && ctx.accounts.token_program.is_some(),
+                FleetWarsError::MissingTokenAccounts
+            );
+            mint.key()
+        }
+        None => Pubkey::default(),
+    };
+
+    // The id is whatever the creator's counter says; init has already claimed that PDA.
+    let profile = &mut ctx.accounts.profile;
+    let game_id = profile.games_created;
+    profile.init_if_new(ctx.accounts.player1.key(), ctx.bumps.profile);
+    profile.games_created = game_id + 1;
+
+    let game_key = ctx.accounts.game.key();
     let game = &mut ctx.accounts.game;
 
     game.player1 = ctx.accounts.player1.key();
     game.player2 = Pubkey::default();
+    game.invited = invited.unwrap_or_default();
     game.p1_board_hash = board_hash;
     game.p2_board_hash = [0u8; 32];
+    game.mint = mint;
     game.p1_shots = 0;
     game.p2_shots = 0;
     game.p1_declared_hits = 0;
     game.p2_declared_hits = 0;
     game.p1_board = 0;
     game.p2_board = 0;
+    game.pending_shots = 0;
+    game.p1_sunk_cells = [255; MAX_SHIPS];
+    game.p2_sunk_cells = [255; MAX_SHIPS];
+    game.fleet = fleet_lengths;
+    game.fleet_size = fleet.len() as u8;
+    game.total_ship_cells = fleet.iter().sum();
+    game.board_rows = board_rows;
+    game.board_cols = board_cols;
+    game.p1_sunk_ships = 0;
+    game.p2_sunk_ships = 0;
     game.wager = wager;
+    game.bond = bond;
+    game.rated = rated;
     game.game_id = game_id;
+    game.last_action_ts = Clock::get()?.unix_timestamp;
+    game.turn_timeout_secs = turn_timeout_secs;
+    game.reveal_deadline = 0;
     game.last_shot_cell = 255;
     game.turn_state = turn_state::P1_FIRE;
     game.game_state = game_state::WAITING_FOR_PLAYER;
+    game.commitment_mode = commitment_mode;
+    game.game_mode = game_mode;
     game.p1_hits_on_p2 = 0;
     game.p2_hits_on_p1 = 0;
     game.p1_revealed = false;
     game.p2_revealed = false;
+    game.p1_conceded = false;
+    game.p2_conceded = false;
+    game.p1_cheated = false;
+    game.p2_cheated = false;
     game.winner = 0;
     game.bump = ctx.bumps.game;
 
-    if wager > 0 {
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.player1.to_account_info(),
-                    to: ctx.accounts.game.to_account_info(),
-                },
-            ),
-            wager,
-        )?;
+    // Only public games are listed; an invite-only game would just be noise in the lobby.
+    game.lobby = Pubkey::default();
+    if let Some(page) = ctx.accounts.lobby.as_mut() {
+        require!(game.invited == Pubkey::default(), FleetWarsError::InvalidLobby);
+        lobby::register(page, game_key, game)?;
+        game.lobby = page.key();
     }
 
+    escrow::deposit(
+        &ctx.accounts.game,
+        &ctx.accounts.player1,
+        ctx.accounts.player1_token.as_ref(),
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+        stake,
+    )?;
+
     msg!("Game {} created by {}", game_id, ctx.accounts.player1.key());
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
 pub struct CreateGame<'info> {
+    #[account(
+        init_if_needed,
+        payer = player1,
+        space = PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, player1.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
     #[account(
         init,
         payer = player1,
         space = Game::SPACE,
-        seeds = [GAME_SEED, player1.key().as_ref(), &game_id.to_le_bytes()],
+        seeds = [GAME_SEED, player1.key().as_ref(), &profile.games_created.to_le_bytes()],
         bump
     )]
     pub game: Account<'info, Game>,
     #[account(mut)]
     pub player1: Signer<'info>,
     pub system_program: Program<'info, System>,
+    /// Optional — set to wager an SPL token instead of lamports
+    pub mint: Option<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = player1,
+        seeds = [VAULT_SEED, game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = game,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player1_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// Optional — lobby page of the wager's bracket to list the game in
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
 }
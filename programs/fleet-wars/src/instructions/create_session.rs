@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{
+    Game, SessionToken, game_state, GAME_SEED, MAX_SESSION_LAMPORTS, MAX_SESSION_SECS,
+    SESSION_SEED,
+};
+use crate::error::FleetWarsError;
+
+/// A player authorizes `session_key` to sign ER moves on their behalf in this game until
+/// `expires_at`, and funds it for transaction fees. Calling again rotates the key or extends
+/// the expiry; funding across all calls is capped at `MAX_SESSION_LAMPORTS`.
+///
+/// The game is read from raw data because it is usually already delegated (and so owned by
+/// the delegation program) by the time player2 can create a session.
+pub fn handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expires_at: i64,
+    lamports: u64,
+) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let game = {
+        let game_data = ctx.accounts.game.try_borrow_data()?;
+        let mut game_slice: &[u8] = &game_data;
+        Game::try_deserialize(&mut game_slice)?
+    };
+    let game_pda = Pubkey::create_program_address(
+        &[GAME_SEED, game.player1.as_ref(), &game.game_id.to_le_bytes(), &[game.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| FleetWarsError::InvalidGameState)?;
+    require_keys_eq!(game_pda, game_key, FleetWarsError::InvalidGameState);
+
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        game.game_state == game_state::WAITING_FOR_PLAYER || game.game_state == game_state::ACTIVE,
+        FleetWarsError::InvalidGameState
+    );
+    require!(
+        authority == game.player1 || authority == game.player2,
+        FleetWarsError::Unauthorized
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let session = &mut ctx.accounts.session;
+    let funded = session
+        .lamports_funded
+        .checked_add(lamports)
+        .ok_or(FleetWarsError::InvalidSessionLimits)?;
+    require!(
+        expires_at > now
+            && expires_at <= now.saturating_add(MAX_SESSION_SECS)
+            && funded <= MAX_SESSION_LAMPORTS,
+        FleetWarsError::InvalidSessionLimits
+    );
+
+    session.authority = authority;
+    session.game = game_key;
+    session.session_key = session_key;
+    session.expires_at = expires_at;
+    session.lamports_funded = funded;
+    session.bump = ctx.bumps.session;
+
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.session_signer.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+
+    msg!(
+        "Session {} for {} in game {} until {}",
+        session_key,
+        authority,
+        game.game_id,
+        expires_at
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    /// CHECK: Game PDA, possibly delegated; deserialized and seed-checked in the handler
+    pub game: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SessionToken::SPACE,
+        seeds = [SESSION_SEED, game.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionToken>,
+    /// CHECK: The ephemeral session key; only receives its fee funding
+    #[account(mut, address = session_key)]
+    pub session_signer: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
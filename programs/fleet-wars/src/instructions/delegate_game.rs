@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 use ephemeral_rollups_sdk::anchor::delegate;
 use ephemeral_rollups_sdk::cpi::DelegateConfig;
-use crate::state::{Game, game_state, GAME_SEED};
+use crate::state::{Config, Game, game_state, CONFIG_SEED, GAME_SEED};
 use crate::error::FleetWarsError;
 
-pub fn handler(ctx: Context<DelegateGame>, game_id: u64) -> Result<()> {
+pub fn handler(ctx: Context<DelegateGame>, game_id: u64, commit_frequency_ms: u32) -> Result<()> {
     let game = &ctx.accounts.pda;
 
     require!(
@@ -12,37 +12,64 @@ pub fn handler(ctx: Context<DelegateGame>, game_id: u64) -> Result<()> {
         FleetWarsError::InvalidGameState
     );
     require!(
-        ctx.accounts.player1.key() == game.player1,
+        ctx.accounts.player.key() == game.player1 || ctx.accounts.player.key() == game.player2,
         FleetWarsError::Unauthorized
     );
 
+    let delegate_config = delegate_config(
+        &ctx.accounts.config,
+        commit_frequency_ms,
+        ctx.accounts.validator.as_ref(),
+    )?;
+
     let game_id_bytes = game_id.to_le_bytes();
     ctx.accounts.delegate_pda(
-        &ctx.accounts.player1,
-        &[GAME_SEED, ctx.accounts.player1.key().as_ref(), &game_id_bytes],
-        DelegateConfig {
-            commit_frequency_ms: 5_000,
-            validator: ctx.accounts.validator.as_ref().map(|v| v.key()),
-        },
+        &ctx.accounts.player,
+        &[GAME_SEED, game.player1.as_ref(), &game_id_bytes],
+        delegate_config,
     )?;
 
     msg!("Game {} delegated to Ephemeral Rollup", game_id);
     Ok(())
 }
 
+/// Checks the requested commit frequency and validator against the config's delegation
+/// policy.
+pub(crate) fn delegate_config(
+    config: &Config,
+    commit_frequency_ms: u32,
+    validator: Option<&AccountInfo>,
+) -> Result<DelegateConfig> {
+    require!(
+        (config.min_commit_frequency_ms..=config.max_commit_frequency_ms)
+            .contains(&commit_frequency_ms),
+        FleetWarsError::InvalidCommitFrequency
+    );
+    let validator = validator.map(|v| v.key());
+    require!(config.allows_validator(validator), FleetWarsError::ValidatorNotAllowed);
+
+    Ok(DelegateConfig {
+        commit_frequency_ms,
+        validator,
+    })
+}
+
 #[delegate]
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
 pub struct DelegateGame<'info> {
+    /// Either player; pays for the delegation accounts
     #[account(mut)]
-    pub player1: Signer<'info>,
-    /// CHECK: Optional validator override
+    pub player: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Optional validator override, checked against the config allowlist
     pub validator: Option<AccountInfo<'info>>,
     /// CHECK: Game PDA to delegate
     #[account(
         mut,
         del,
-        seeds = [GAME_SEED, player1.key().as_ref(), &game_id.to_le_bytes()],
+        seeds = [GAME_SEED, pda.player1.as_ref(), &game_id.to_le_bytes()],
         bump = pda.bump
     )]
     pub pda: Account<'info, Game>,
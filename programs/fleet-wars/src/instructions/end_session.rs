@@ -4,23 +4,46 @@ use crate::state::{Game, game_state};
 use crate::error::FleetWarsError;
 
 /// Called from the ER by either player to manually end the session (e.g. opponent abandoned game).
+/// A game still in battle moves to the reveal phase: the caller wins if the opponent is on move
+/// and past `turn_timeout_secs` (as with claim_timeout), otherwise the caller forfeits.
 pub fn handler(ctx: Context<EndSession>) -> Result<()> {
-    let (game_state_value, p1, p2) = {
+    let mut game = {
         let game_data = ctx.accounts.game.try_borrow_data()?;
         let mut game_slice: &[u8] = &game_data;
-        let game = Game::try_deserialize(&mut game_slice)?;
-        (game.game_state, game.player1, game.player2)
+        Game::try_deserialize(&mut game_slice)?
     };
 
     require!(
-        game_state_value == game_state::ACTIVE || game_state_value == game_state::WAITING_REVEAL,
+        game.game_state == game_state::ACTIVE || game.game_state == game_state::WAITING_REVEAL,
         FleetWarsError::GameNotActive
     );
     require!(
-        ctx.accounts.player.key() == p1 || ctx.accounts.player.key() == p2,
+        ctx.accounts.player.key() == game.player1 || ctx.accounts.player.key() == game.player2,
         FleetWarsError::Unauthorized
     );
 
+    if game.game_state == game_state::ACTIVE {
+        let now = Clock::get()?.unix_timestamp;
+        let (caller, opponent) = if ctx.accounts.player.key() == game.player1 {
+            (1u8, 2u8)
+        } else {
+            (2u8, 1u8)
+        };
+        let on_move = game.player_on_move().ok_or(FleetWarsError::InvalidGameState)?;
+        // Never a free draw: leaving early concedes the battle unless the opponent stalled.
+        let winner = if on_move == opponent && game.turn_timed_out(now) {
+            caller
+        } else {
+            opponent
+        };
+        game.end_battle(winner, now);
+
+        let mut game_data = ctx.accounts.game.try_borrow_mut_data()?;
+        let mut game_slice: &mut [u8] = &mut game_data;
+        game.try_serialize(&mut game_slice)?;
+        msg!("Game {} ended by P{}, P{} wins, pending reveal", game.game_id, caller, winner);
+    }
+
     commit_and_undelegate_accounts(
         &ctx.accounts.player.to_account_info(),
         vec![&ctx.accounts.game],
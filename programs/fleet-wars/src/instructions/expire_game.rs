@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::lobby;
+use crate::state::{Game, LobbyPage, game_state, LOBBY_EXPIRY_SECS, VAULT_SEED};
+use crate::error::FleetWarsError;
+
+/// Permissionless cleanup of a stale lobby. Anyone may close a game that has waited
+/// longer than `LOBBY_EXPIRY_SECS` for an opponent; the stake and rent go back to player1.
+pub fn handler(ctx: Context<ExpireGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+
+    require!(
+        game.game_state == game_state::WAITING_FOR_PLAYER,
+        FleetWarsError::InvalidGameState
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now > game.last_action_ts.saturating_add(LOBBY_EXPIRY_SECS),
+        FleetWarsError::LobbyNotExpired
+    );
+
+    lobby::unregister(ctx.accounts.lobby.as_mut(), game.key(), game)?;
+
+    escrow::refund_creator(
+        game,
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.player1_token.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.player1.to_account_info(),
+    )?;
+
+    msg!(
+        "Game {} expired by {}, stake refunded to creator",
+        game.game_id,
+        ctx.accounts.caller.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireGame<'info> {
+    #[account(mut, has_one = player1 @ FleetWarsError::Unauthorized, close = player1)]
+    pub game: Account<'info, Game>,
+    /// CHECK: Creator receives the refund; verified against game.player1
+    #[account(mut)]
+    pub player1: AccountInfo<'info>,
+    pub caller: Signer<'info>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player1_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// Optional — the lobby page listing this game, required when `game.lobby` is set
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+}
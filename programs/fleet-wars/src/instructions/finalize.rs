@@ -1,70 +1,80 @@
 use anchor_lang::prelude::*;
-use crate::state::{Game, game_state};
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::settlement;
+use crate::state::{
+    Config, Game, PlayerProfile, Treasury, game_state, CONFIG_SEED, PROFILE_SEED, TREASURY_SEED,
+    VAULT_SEED,
+};
 use crate::error::FleetWarsError;
 
-/// After both players reveal, verify declared hits match actual boards and pay out winner.
+/// After both players reveal (or concede), verify declared hits match actual boards and
+/// settle the pot: pay the winner, refund a draw, or slash a game where both players cheated.
 pub fn handler(ctx: Context<Finalize>) -> Result<()> {
-    let (game_id, final_winner, winner_key, p1_cheated, p2_cheated, total_pot) = {
+    {
         let game = &mut ctx.accounts.game;
 
         require!(
             game.game_state == game_state::WAITING_REVEAL,
             FleetWarsError::InvalidGameState
         );
-        require!(game.p1_revealed && game.p2_revealed, FleetWarsError::GameNotReady);
+        require!(
+            (game.p1_revealed || game.p1_conceded) && (game.p2_revealed || game.p2_conceded),
+            FleetWarsError::GameNotReady
+        );
 
-        // Verify P2's declarations: P2 declared hits on P2's board when P1 shot there.
-        // p2_declared_hits must equal p1_shots & p2_board (cells P1 shot that are actually ships).
-        let expected_p2_hits = game.p1_shots & game.p2_board;
-        let p2_cheated = game.p2_declared_hits != expected_p2_hits;
+        // A player who conceded has no board to check and is treated as a cheater. Honest
+        // players get their bond back; a cheater's bond goes to the honest opponent, or to
+        // the treasury when both cheated.
+        let (p1_cheated, p2_cheated) = (settlement::cheated(game, 1), settlement::cheated(game, 2));
+        settlement::decide(game, p1_cheated, p2_cheated);
+    }
 
-        // Verify P1's declarations: P1 declared hits on P1's board when P2 shot there.
-        let expected_p1_hits = game.p2_shots & game.p1_board;
-        let p1_cheated = game.p1_declared_hits != expected_p1_hits;
+    let game = &ctx.accounts.game;
+    let vault = ctx.accounts.vault.as_ref();
+    let token_program = ctx.accounts.token_program.as_ref();
+    let treasury_to = match &ctx.accounts.treasury_token {
+        Some(treasury_token) => treasury_token.clone(),
+        None => ctx.accounts.treasury.to_account_info(),
+    };
+    let treasury_key = ctx.accounts.treasury.key();
 
-        // Determine winner based on cheat detection
-        let final_winner = if p2_cheated && !p1_cheated {
-            // P2 lied about hits — P1 wins regardless of declared winner
-            1u8
-        } else if p1_cheated && !p2_cheated {
-            // P1 lied about hits — P2 wins
-            2u8
-        } else {
-            // Nobody cheated OR both cheated — keep declared winner from ER
-            game.winner
-        };
+    // The protocol fee goes to the treasury PDA (or its token account).
+    let (p1_paid, p2_paid, treasury_paid) = settlement::split(game, &ctx.accounts.config)?;
+    let p1_to = &ctx.accounts.p1_payout;
+    let p2_to = &ctx.accounts.p2_payout;
+    escrow::release(game, vault, token_program, p1_to, game.player1, p1_paid)?;
+    escrow::release(game, vault, token_program, p2_to, game.player2, p2_paid)?;
+    escrow::release(game, vault, token_program, &treasury_to, treasury_key, treasury_paid)?;
 
-        let winner_key = if final_winner == 1 {
-            game.player1
-        } else {
-            game.player2
-        };
-        let total_pot = game.wager.saturating_mul(2);
+    PlayerProfile::record_settlement(
+        (&mut ctx.accounts.p1_profile, ctx.bumps.p1_profile),
+        (&mut ctx.accounts.p2_profile, ctx.bumps.p2_profile),
+        game,
+        p1_paid,
+        p2_paid,
+    );
 
-        game.winner = final_winner;
-        game.game_state = game_state::FINISHED;
+    let (game_id, outcome, final_winner) = (game.game_id, game.game_state, game.winner);
+    let (p1_cheated, p2_cheated) = (game.p1_cheated, game.p2_cheated);
 
-        (
-            game.game_id,
-            final_winner,
-            winner_key,
-            p1_cheated,
-            p2_cheated,
-            total_pot,
-        )
-    };
-
-    if total_pot > 0 {
-        require!(winner_key == ctx.accounts.winner.key(), FleetWarsError::Unauthorized);
-        **ctx.accounts.game.to_account_info().try_borrow_mut_lamports()? -= total_pot;
-        **ctx.accounts.winner.try_borrow_mut_lamports()? += total_pot;
+    // Optional auto-close: passing player1 reclaims the rent in the same transaction.
+    if let Some(player1) = &ctx.accounts.player1 {
+        require!(player1.key() == ctx.accounts.game.player1, FleetWarsError::Unauthorized);
+        escrow::close_vault(
+            &ctx.accounts.game,
+            ctx.accounts.vault.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            player1,
+        )?;
+        ctx.accounts.game.close(player1.to_account_info())?;
     }
 
     msg!(
-        "Game {} finalized. Winner: P{} ({}). P1_cheated={}, P2_cheated={}",
+        "Game {} finalized. State: {}, winner: P{}. P1_cheated={}, P2_cheated={}",
         game_id,
+        outcome,
         final_winner,
-        winner_key,
         p1_cheated,
         p2_cheated
     );
@@ -76,8 +86,43 @@ pub fn handler(ctx: Context<Finalize>) -> Result<()> {
 pub struct Finalize<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
-    /// CHECK: Winner receives the pot
+    /// CHECK: Player1's wallet, or their token account for token games; checked in escrow::release
+    #[account(mut)]
+    pub p1_payout: AccountInfo<'info>,
+    /// CHECK: Player2's wallet, or their token account for token games; checked in escrow::release
+    #[account(mut)]
+    pub p2_payout: AccountInfo<'info>,
+    /// Pays for any player profile created here
     #[account(mut)]
-    pub winner: AccountInfo<'info>,
     pub caller: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, game.player1.as_ref()],
+        bump
+    )]
+    pub p1_profile: Account<'info, PlayerProfile>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, game.player2.as_ref()],
+        bump
+    )]
+    pub p2_profile: Account<'info, PlayerProfile>,
+    /// CHECK: Optional — when present the game is closed to player1 after payout
+    #[account(mut)]
+    pub player1: Option<AccountInfo<'info>>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: Treasury-owned token account receiving the fee in token games; checked in escrow::release
+    #[account(mut)]
+    pub treasury_token: Option<AccountInfo<'info>>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Game, SessionToken, game_mode, game_state, turn_state};
+use crate::error::FleetWarsError;
+use crate::session;
+
+/// Salvo mode: the active player fires one shot per ship they still have afloat
+/// (capped by the cells they have not shot yet). The opponent answers with `respond_salvo`.
+pub fn handler(ctx: Context<FireSalvo>, cells: Vec<u8>) -> Result<()> {
+    let player = session::acting_player(
+        &ctx.accounts.player,
+        ctx.accounts.session.as_ref(),
+        ctx.accounts.game.key(),
+    )?;
+    let game = &mut ctx.accounts.game;
+
+    require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
+    require!(game.game_mode == game_mode::SALVO, FleetWarsError::WrongGameMode);
+
+    let (shooter, shots, own_sunk, respond_turn) = match game.turn_state {
+        t if t == turn_state::P1_FIRE => {
+            (game.player1, game.p1_shots, game.p1_sunk_ships, turn_state::P2_RESPOND)
+        }
+        t if t == turn_state::P2_FIRE => {
+            (game.player2, game.p2_shots, game.p2_sunk_ships, turn_state::P1_RESPOND)
+        }
+        _ => return Err(FleetWarsError::NotYourTurn.into()),
+    };
+    require!(player == shooter, FleetWarsError::NotYourTurn);
+
+    let afloat = game.fleet_size as u32 - own_sunk.count_ones();
+    let unshot = game.board_cells() as u32 - shots.count_ones();
+    require!(
+        cells.len() as u32 == afloat.min(unshot),
+        FleetWarsError::InvalidSalvoSize
+    );
+
+    let mut salvo = 0u128;
+    for &cell in &cells {
+        require!(cell < game.board_cells(), FleetWarsError::InvalidCell);
+        let cell_bit = 1u128 << cell;
+        require!((shots | salvo) & cell_bit == 0, FleetWarsError::CellAlreadyShot);
+        salvo |= cell_bit;
+    }
+
+    if respond_turn == turn_state::P2_RESPOND {
+        game.p1_shots |= salvo;
+    } else {
+        game.p2_shots |= salvo;
+    }
+    game.pending_shots = salvo;
+    game.turn_state = respond_turn;
+    game.last_action_ts = Clock::get()?.unix_timestamp;
+
+    msg!("Salvo of {} shots fired", cells.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FireSalvo<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub player: Signer<'info>,
+    /// Optional — when present, `player` is this session's key acting for its authority
+    pub session: Option<Account<'info, SessionToken>>,
+}
@@ -1,25 +1,32 @@
 use anchor_lang::prelude::*;
-use crate::state::{Game, game_state, turn_state};
+use crate::state::{Game, SessionToken, game_mode, game_state, turn_state};
 use crate::error::FleetWarsError;
+use crate::session;
 
 pub fn handler(ctx: Context<FireShot>, cell: u8) -> Result<()> {
+    let player = session::acting_player(
+        &ctx.accounts.player,
+        ctx.accounts.session.as_ref(),
+        ctx.accounts.game.key(),
+    )?;
     let game = &mut ctx.accounts.game;
 
     require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
-    require!(cell < 64, FleetWarsError::InvalidCell);
+    require!(game.game_mode == game_mode::CLASSIC, FleetWarsError::WrongGameMode);
+    require!(cell < game.board_cells(), FleetWarsError::InvalidCell);
 
-    let cell_bit = 1u64 << cell;
+    let cell_bit = 1u128 << cell;
 
     match game.turn_state {
         t if t == turn_state::P1_FIRE => {
-            require!(ctx.accounts.player.key() == game.player1, FleetWarsError::NotYourTurn);
+            require!(player == game.player1, FleetWarsError::NotYourTurn);
             require!(game.p1_shots & cell_bit == 0, FleetWarsError::CellAlreadyShot);
             game.p1_shots |= cell_bit;
             game.last_shot_cell = cell;
             game.turn_state = turn_state::P2_RESPOND;
         }
         t if t == turn_state::P2_FIRE => {
-            require!(ctx.accounts.player.key() == game.player2, FleetWarsError::NotYourTurn);
+            require!(player == game.player2, FleetWarsError::NotYourTurn);
             require!(game.p2_shots & cell_bit == 0, FleetWarsError::CellAlreadyShot);
             game.p2_shots |= cell_bit;
             game.last_shot_cell = cell;
@@ -27,8 +34,14 @@ pub fn handler(ctx: Context<FireShot>, cell: u8) -> Result<()> {
         }
         _ => return Err(FleetWarsError::NotYourTurn.into()),
     }
+    game.last_action_ts = Clock::get()?.unix_timestamp;
 
-    msg!("Shot fired at cell {} (row={}, col={})", cell, cell / 8, cell % 8);
+    msg!(
+        "Shot fired at cell {} (row={}, col={})",
+        cell,
+        cell / game.board_cols,
+        cell % game.board_cols
+    );
     Ok(())
 }
 
@@ -37,4 +50,6 @@ pub struct FireShot<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
     pub player: Signer<'info>,
+    /// Optional — when present, `player` is this session's key acting for its authority
+    pub session: Option<Account<'info, SessionToken>>,
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{Game, game_state};
+use crate::error::FleetWarsError;
+
+/// A player who cannot reveal (lost salt, bad commitment) concedes instead. The
+/// concession is recorded, finalize treats it as cheating and settles without that board.
+pub fn handler(ctx: Context<ForfeitReveal>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    require!(
+        game.game_state == game_state::WAITING_REVEAL,
+        FleetWarsError::InvalidGameState
+    );
+
+    let player = ctx.accounts.player.key();
+    if player == game.player1 {
+        require!(!game.p1_revealed && !game.p1_conceded, FleetWarsError::InvalidGameState);
+        game.p1_conceded = true;
+        msg!("Player1 conceded the reveal");
+    } else if player == game.player2 {
+        require!(!game.p2_revealed && !game.p2_conceded, FleetWarsError::InvalidGameState);
+        game.p2_conceded = true;
+        msg!("Player2 conceded the reveal");
+    } else {
+        return Err(FleetWarsError::Unauthorized.into());
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ForfeitReveal<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    pub player: Signer<'info>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::program::FleetWars;
+use crate::state::{
+    Config, Treasury, CONFIG_SEED, DEFAULT_MAX_COMMIT_FREQUENCY_MS,
+    DEFAULT_MIN_COMMIT_FREQUENCY_MS, MAX_FEE_BPS, TREASURY_SEED,
+};
+use crate::error::FleetWarsError;
+
+/// Creates the protocol config and treasury. Only the program's upgrade authority may
+/// call it, and only once.
+pub fn handler(ctx: Context<InitConfig>, treasury_authority: Pubkey, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, FleetWarsError::InvalidFee);
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury_authority = treasury_authority;
+    config.fee_bps = fee_bps;
+    config.min_commit_frequency_ms = DEFAULT_MIN_COMMIT_FREQUENCY_MS;
+    config.max_commit_frequency_ms = DEFAULT_MAX_COMMIT_FREQUENCY_MS;
+    config.allowed_validators = Vec::new();
+    config.bump = ctx.bumps.config;
+
+    ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+    msg!("Config initialized: fee {} bps, treasury authority {}", fee_bps, treasury_authority);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(init, payer = admin, space = Config::SPACE, seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(init, payer = admin, space = Treasury::SPACE, seeds = [TREASURY_SEED], bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ FleetWarsError::Unauthorized
+    )]
+    pub program: Program<'info, FleetWars>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ FleetWarsError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
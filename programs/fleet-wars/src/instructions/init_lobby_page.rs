@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{LobbyPage, LOBBY_SEED, MAX_WAGER_BRACKET};
+use crate::error::FleetWarsError;
+
+/// Permissionless: anyone may open a new lobby page for a wager bracket when the
+/// existing ones fill up.
+pub fn handler(ctx: Context<InitLobbyPage>, bracket: u8, page: u16) -> Result<()> {
+    require!(bracket <= MAX_WAGER_BRACKET, FleetWarsError::InvalidLobby);
+
+    let lobby = &mut ctx.accounts.lobby;
+    lobby.bracket = bracket;
+    lobby.page = page;
+    lobby.entries = Vec::new();
+    lobby.bump = ctx.bumps.lobby;
+
+    msg!("Lobby page {} opened for wager bracket {}", page, bracket);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bracket: u8, page: u16)]
+pub struct InitLobbyPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = LobbyPage::SPACE,
+        seeds = [LOBBY_SEED, &[bracket], &page.to_le_bytes()],
+        bump
+    )]
+    pub lobby: Account<'info, LobbyPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use ephemeral_rollups_sdk::anchor::delegate;
+use crate::instructions::delegate_game::delegate_config;
+use crate::instructions::join_game::join;
+use crate::lobby;
+use crate::state::{Config, Game, LobbyPage, CONFIG_SEED, GAME_SEED, VAULT_SEED};
+
+/// Player2 joins and delegates in one transaction, so the game goes ACTIVE and lands on
+/// the ER without waiting for a separate `delegate_game`.
+pub fn handler(
+    ctx: Context<JoinAndDelegate>,
+    board_hash: [u8; 32],
+    commit_frequency_ms: u32,
+) -> Result<()> {
+    lobby::unregister(ctx.accounts.lobby.as_mut(), ctx.accounts.pda.key(), &ctx.accounts.pda)?;
+    join(
+        &mut ctx.accounts.pda,
+        &ctx.accounts.player2,
+        board_hash,
+        ctx.accounts.player2_token.as_ref(),
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+
+    let delegate_config = delegate_config(
+        &ctx.accounts.config,
+        commit_frequency_ms,
+        ctx.accounts.validator.as_ref(),
+    )?;
+
+    // Delegation copies the account data into the buffer, so persist the join first.
+    ctx.accounts.pda.exit(&crate::ID)?;
+
+    let game = &ctx.accounts.pda;
+    let game_id_bytes = game.game_id.to_le_bytes();
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.player2,
+        &[GAME_SEED, game.player1.as_ref(), &game_id_bytes],
+        delegate_config,
+    )?;
+
+    msg!("Game {} delegated to Ephemeral Rollup", game.game_id);
+    Ok(())
+}
+
+#[delegate]
+#[derive(Accounts)]
+pub struct JoinAndDelegate<'info> {
+    #[account(mut)]
+    pub player2: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Optional validator override, checked against the config allowlist
+    pub validator: Option<AccountInfo<'info>>,
+    /// CHECK: Game PDA to join and delegate
+    #[account(
+        mut,
+        del,
+        seeds = [GAME_SEED, pda.player1.as_ref(), &pda.game_id.to_le_bytes()],
+        bump = pda.bump
+    )]
+    pub pda: Account<'info, Game>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds = [VAULT_SEED, pda.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player2_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// Optional — the lobby page listing this game, required when `game.lobby` is set
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+}
@@ -1,12 +1,34 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use crate::state::{Game, game_state};
+use anchor_spl::token::{Token, TokenAccount};
+use crate::escrow;
+use crate::lobby;
+use crate::state::{Game, LobbyPage, game_state, VAULT_SEED};
 use crate::error::FleetWarsError;
 
 pub fn handler(ctx: Context<JoinGame>, board_hash: [u8; 32]) -> Result<()> {
-    let game = &mut ctx.accounts.game;
-    let game_account_info = game.to_account_info();
+    lobby::unregister(ctx.accounts.lobby.as_mut(), ctx.accounts.game.key(), &ctx.accounts.game)?;
+    join(
+        &mut ctx.accounts.game,
+        &ctx.accounts.player2,
+        board_hash,
+        ctx.accounts.player2_token.as_ref(),
+        ctx.accounts.vault.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )
+}
 
+/// Seats player2, activates the game and escrows their stake. Shared with
+/// `join_and_delegate`.
+pub(crate) fn join<'info>(
+    game: &mut Account<'info, Game>,
+    player2: &Signer<'info>,
+    board_hash: [u8; 32],
+    player2_token: Option<&Account<'info, TokenAccount>>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
     require!(
         game.game_state == game_state::WAITING_FOR_PLAYER,
         FleetWarsError::InvalidGameState
@@ -16,28 +38,32 @@ pub fn handler(ctx: Context<JoinGame>, board_hash: [u8; 32]) -> Result<()> {
         FleetWarsError::InvalidGameState
     );
     require!(
-        ctx.accounts.player2.key() != game.player1,
+        player2.key() != game.player1,
         FleetWarsError::Unauthorized
     );
+    require!(
+        game.invited == Pubkey::default() || game.invited == player2.key(),
+        FleetWarsError::NotInvited
+    );
 
-    game.player2 = ctx.accounts.player2.key();
+    game.player2 = player2.key();
     game.p2_board_hash = board_hash;
     game.game_state = game_state::ACTIVE;
+    game.last_action_ts = Clock::get()?.unix_timestamp;
 
-    if game.wager > 0 {
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.player2.to_account_info(),
-                    to: game_account_info,
-                },
-            ),
-            game.wager,
-        )?;
-    }
+    let (game_id, stake) = (game.game_id, game.stake());
 
-    msg!("Player2 {} joined game {}", ctx.accounts.player2.key(), game.game_id);
+    escrow::deposit(
+        game,
+        player2,
+        player2_token,
+        vault,
+        token_program,
+        system_program,
+        stake,
+    )?;
+
+    msg!("Player2 {} joined game {}", player2.key(), game_id);
     Ok(())
 }
 
@@ -48,4 +74,12 @@ pub struct JoinGame<'info> {
     #[account(mut)]
     pub player2: Signer<'info>,
     pub system_program: Program<'info, System>,
+    #[account(mut, seeds = [VAULT_SEED, game.key().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player2_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// Optional — the lobby page listing this game, required when `game.lobby` is set
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
 }
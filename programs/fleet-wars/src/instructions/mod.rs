@@ -1,17 +1,53 @@
+pub mod init_config;
+pub mod update_config;
+pub mod set_delegation_policy;
+pub mod withdraw_treasury;
 pub mod create_game;
+pub mod init_lobby_page;
 pub mod join_game;
+pub mod join_and_delegate;
+pub mod reopen_game;
+pub mod cancel_game;
+pub mod expire_game;
 pub mod delegate_game;
+pub mod create_session;
+pub mod revoke_session;
 pub mod fire_shot;
 pub mod respond_shot;
+pub mod fire_salvo;
+pub mod respond_salvo;
+pub mod checkpoint_game;
 pub mod end_session;
+pub mod claim_timeout;
 pub mod reveal_board;
+pub mod forfeit_reveal;
 pub mod finalize;
+pub mod claim_unrevealed_forfeit;
+pub mod close_game;
 
+pub use init_config::InitConfig;
+pub use update_config::UpdateConfig;
+pub use set_delegation_policy::SetDelegationPolicy;
+pub use withdraw_treasury::WithdrawTreasury;
 pub use create_game::CreateGame;
+pub use init_lobby_page::InitLobbyPage;
 pub use join_game::JoinGame;
+pub use join_and_delegate::JoinAndDelegate;
+pub use reopen_game::ReopenGame;
+pub use cancel_game::CancelGame;
+pub use expire_game::ExpireGame;
 pub use delegate_game::DelegateGame;
+pub use create_session::CreateSession;
+pub use revoke_session::RevokeSession;
 pub use fire_shot::FireShot;
 pub use respond_shot::RespondShot;
+pub use fire_salvo::FireSalvo;
+pub use respond_salvo::RespondSalvo;
+pub use checkpoint_game::CheckpointGame;
 pub use end_session::EndSession;
+pub use claim_timeout::ClaimTimeout;
 pub use reveal_board::RevealBoard;
+pub use forfeit_reveal::ForfeitReveal;
 pub use finalize::Finalize;
+pub use claim_unrevealed_forfeit::ClaimUnrevealedForfeit;
+pub use close_game::CloseGame;
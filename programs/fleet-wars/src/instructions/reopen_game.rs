@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::lobby;
+use crate::state::{Game, LobbyPage, game_state};
+use crate::error::FleetWarsError;
+
+/// Creator withdraws the invite on an unjoined game, opening it to any opponent, and
+/// optionally lists it in a lobby page now that it is public.
+pub fn handler(ctx: Context<ReopenGame>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let game = &mut ctx.accounts.game;
+
+    require!(
+        game.game_state == game_state::WAITING_FOR_PLAYER,
+        FleetWarsError::InvalidGameState
+    );
+
+    game.invited = Pubkey::default();
+
+    if let Some(page) = ctx.accounts.lobby.as_mut() {
+        require!(game.lobby == Pubkey::default(), FleetWarsError::InvalidLobby);
+        lobby::register(page, game_key, game)?;
+        game.lobby = page.key();
+    }
+
+    msg!("Game {} reopened to any opponent", game.game_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReopenGame<'info> {
+    #[account(mut, has_one = player1 @ FleetWarsError::Unauthorized)]
+    pub game: Account<'info, Game>,
+    pub player1: Signer<'info>,
+    /// Optional — lobby page of the wager's bracket to list the game in
+    #[account(mut)]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+}
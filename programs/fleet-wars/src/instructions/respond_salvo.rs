@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+use crate::state::{
+    Game, SessionToken, ShotResult, game_mode, game_state, turn_state, MAX_SHIPS,
+    REVEAL_WINDOW_SECS,
+};
+use crate::error::FleetWarsError;
+use crate::session;
+use crate::instructions::respond_shot::record_sunk;
+
+/// Salvo mode: the opponent answers every shot of the pending salvo at once.
+/// `results[i]` answers the i-th lowest pending cell index.
+pub fn handler(ctx: Context<RespondSalvo>, results: Vec<ShotResult>) -> Result<()> {
+    let player = session::acting_player(
+        &ctx.accounts.player,
+        ctx.accounts.session.as_ref(),
+        ctx.accounts.game.key(),
+    )?;
+    let game: &mut Game = &mut ctx.accounts.game;
+
+    require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
+    require!(game.game_mode == game_mode::SALVO, FleetWarsError::WrongGameMode);
+    require!(
+        results.len() as u32 == game.pending_shots.count_ones(),
+        FleetWarsError::InvalidSalvoSize
+    );
+
+    let fleet_size = game.fleet_size;
+    let sunk_count;
+
+    let game_over = match game.turn_state {
+        t if t == turn_state::P2_RESPOND => {
+            require!(player == game.player2, FleetWarsError::NotYourTurn);
+            sunk_count = answer_salvo(
+                &results,
+                game.pending_shots,
+                fleet_size,
+                &mut game.p2_declared_hits,
+                &mut game.p1_hits_on_p2,
+                &mut game.p2_sunk_ships,
+                &mut game.p2_sunk_cells,
+            )?;
+            if game.p1_hits_on_p2 >= game.total_ship_cells {
+                game.winner = 1;
+                true
+            } else {
+                game.turn_state = turn_state::P2_FIRE;
+                false
+            }
+        }
+        t if t == turn_state::P1_RESPOND => {
+            require!(player == game.player1, FleetWarsError::NotYourTurn);
+            sunk_count = answer_salvo(
+                &results,
+                game.pending_shots,
+                fleet_size,
+                &mut game.p1_declared_hits,
+                &mut game.p2_hits_on_p1,
+                &mut game.p1_sunk_ships,
+                &mut game.p1_sunk_cells,
+            )?;
+            if game.p2_hits_on_p1 >= game.total_ship_cells {
+                game.winner = 2;
+                true
+            } else {
+                game.turn_state = turn_state::P1_FIRE;
+                false
+            }
+        }
+        _ => return Err(FleetWarsError::NotYourTurn.into()),
+    };
+    game.pending_shots = 0;
+    let now = Clock::get()?.unix_timestamp;
+    game.last_action_ts = now;
+
+    msg!("Salvo answered: {} ships sunk", sunk_count);
+
+    if game_over {
+        game.game_state = game_state::WAITING_REVEAL;
+        game.reveal_deadline = now.saturating_add(REVEAL_WINDOW_SECS);
+        msg!("Game over — winner declared as P{}, pending reveal", game.winner);
+
+        // Hand the finished game back to L1 for reveal and settlement. The account is
+        // serialized first so the commit carries the final state.
+        ctx.accounts.game.exit(&crate::ID)?;
+        commit_and_undelegate_accounts(
+            &ctx.accounts.player.to_account_info(),
+            vec![&ctx.accounts.game.to_account_info()],
+            &ctx.accounts.magic_context,
+            &ctx.accounts.magic_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Applies the responder's answers to the `pending` salvo to their own masks: each hit is
+/// marked in `declared_hits` and counted in `hits`, each sinking goes through `record_sunk`.
+/// Returns how many ships the salvo sank.
+fn answer_salvo(
+    results: &[ShotResult],
+    mut pending: u128,
+    fleet_size: u8,
+    declared_hits: &mut u128,
+    hits: &mut u8,
+    sunk_ships: &mut u8,
+    sunk_cells: &mut [u8; MAX_SHIPS],
+) -> Result<u8> {
+    let mut sunk_count = 0u8;
+    for result in results {
+        let cell = pending.trailing_zeros() as u8;
+        pending &= pending - 1;
+        if let ShotResult::Sunk { ship_id } = *result {
+            record_sunk(sunk_ships, sunk_cells, fleet_size, ship_id, cell)?;
+            sunk_count += 1;
+        }
+        if *result != ShotResult::Miss {
+            *declared_hits |= 1u128 << cell;
+            *hits = hits.saturating_add(1);
+        }
+    }
+    Ok(sunk_count)
+}
+
+#[derive(Accounts)]
+pub struct RespondSalvo<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// Optional — when present, `player` is this session's key acting for its authority
+    pub session: Option<Account<'info, SessionToken>>,
+    /// CHECK: MagicBlock magic context
+    #[account(mut, address = ephemeral_rollups_sdk::consts::MAGIC_CONTEXT_ID)]
+    pub magic_context: AccountInfo<'info>,
+    /// CHECK: MagicBlock magic program
+    #[account(address = ephemeral_rollups_sdk::consts::MAGIC_PROGRAM_ID)]
+    pub magic_program: AccountInfo<'info>,
+}
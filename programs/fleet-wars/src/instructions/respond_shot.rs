@@ -1,24 +1,66 @@
 use anchor_lang::prelude::*;
-use crate::state::{Game, game_state, turn_state, TOTAL_SHIP_CELLS};
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+use crate::state::{
+    CellProof, Game, SessionToken, ShotResult, commitment_mode, game_mode, game_state,
+    turn_state, MAX_SHIPS, REVEAL_WINDOW_SECS,
+};
 use crate::error::FleetWarsError;
+use crate::session;
+use crate::merkle;
 
-pub fn handler(ctx: Context<RespondShot>, hit: bool) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+pub fn handler(
+    ctx: Context<RespondShot>,
+    result: ShotResult,
+    proof: Option<CellProof>,
+) -> Result<()> {
+    let player = session::acting_player(
+        &ctx.accounts.player,
+        ctx.accounts.session.as_ref(),
+        ctx.accounts.game.key(),
+    )?;
+    let game: &mut Game = &mut ctx.accounts.game;
 
     require!(game.game_state == game_state::ACTIVE, FleetWarsError::GameNotActive);
+    require!(game.game_mode == game_mode::CLASSIC, FleetWarsError::WrongGameMode);
 
     let cell = game.last_shot_cell;
-    require!(cell < 64, FleetWarsError::InvalidCell);
-    let cell_bit = 1u64 << cell;
+    require!(cell < game.board_cells(), FleetWarsError::InvalidCell);
+    let cell_bit = 1u128 << cell;
+    let hit = result != ShotResult::Miss;
+    let fleet_size = game.fleet_size;
+
+    // In Merkle mode the answer is checked against the responder's committed root now,
+    // instead of waiting for finalize to compare declarations with the revealed board.
+    if game.commitment_mode == commitment_mode::MERKLE {
+        let root = match game.turn_state {
+            t if t == turn_state::P2_RESPOND => game.p2_board_hash,
+            t if t == turn_state::P1_RESPOND => game.p1_board_hash,
+            _ => return Err(FleetWarsError::NotYourTurn.into()),
+        };
+        let proof = proof.ok_or(FleetWarsError::InvalidCellProof)?;
+        require!(
+            merkle::verify_cell(&root, cell, hit, &proof.cell_salt, &proof.siblings),
+            FleetWarsError::InvalidCellProof
+        );
+    }
 
     let game_over = match game.turn_state {
         t if t == turn_state::P2_RESPOND => {
-            require!(ctx.accounts.player.key() == game.player2, FleetWarsError::NotYourTurn);
+            require!(player == game.player2, FleetWarsError::NotYourTurn);
+            if let ShotResult::Sunk { ship_id } = result {
+                record_sunk(
+                    &mut game.p2_sunk_ships,
+                    &mut game.p2_sunk_cells,
+                    fleet_size,
+                    ship_id,
+                    cell,
+                )?;
+            }
             if hit {
                 game.p2_declared_hits |= cell_bit;
                 game.p1_hits_on_p2 = game.p1_hits_on_p2.saturating_add(1);
             }
-            if game.p1_hits_on_p2 >= TOTAL_SHIP_CELLS {
+            if game.p1_hits_on_p2 >= game.total_ship_cells {
                 game.winner = 1;
                 true
             } else {
@@ -27,12 +69,21 @@ pub fn handler(ctx: Context<RespondShot>, hit: bool) -> Result<()> {
             }
         }
         t if t == turn_state::P1_RESPOND => {
-            require!(ctx.accounts.player.key() == game.player1, FleetWarsError::NotYourTurn);
+            require!(player == game.player1, FleetWarsError::NotYourTurn);
+            if let ShotResult::Sunk { ship_id } = result {
+                record_sunk(
+                    &mut game.p1_sunk_ships,
+                    &mut game.p1_sunk_cells,
+                    fleet_size,
+                    ship_id,
+                    cell,
+                )?;
+            }
             if hit {
                 game.p1_declared_hits |= cell_bit;
                 game.p2_hits_on_p1 = game.p2_hits_on_p1.saturating_add(1);
             }
-            if game.p2_hits_on_p1 >= TOTAL_SHIP_CELLS {
+            if game.p2_hits_on_p1 >= game.total_ship_cells {
                 game.winner = 2;
                 true
             } else {
@@ -42,19 +93,61 @@ pub fn handler(ctx: Context<RespondShot>, hit: bool) -> Result<()> {
         }
         _ => return Err(FleetWarsError::NotYourTurn.into()),
     };
+    let now = Clock::get()?.unix_timestamp;
+    game.last_action_ts = now;
+
+    if let ShotResult::Sunk { ship_id } = result {
+        msg!("Hit at cell {} sunk ship {}", cell, ship_id);
+    }
 
     if game_over {
         game.game_state = game_state::WAITING_REVEAL;
+        game.reveal_deadline = now.saturating_add(REVEAL_WINDOW_SECS);
         msg!("Game over — winner declared as P{}, pending reveal", game.winner);
+
+        // Hand the finished game back to L1 for reveal and settlement. The account is
+        // serialized first so the commit carries the final state.
+        ctx.accounts.game.exit(&crate::ID)?;
+        commit_and_undelegate_accounts(
+            &ctx.accounts.player.to_account_info(),
+            vec![&ctx.accounts.game.to_account_info()],
+            &ctx.accounts.magic_context,
+            &ctx.accounts.magic_program,
+        )?;
     }
 
     Ok(())
 }
 
+/// Marks `ship_id` of the responder's fleet as sunk by the shot at `cell`.
+/// Finalize later checks that ship really occupies `cell` and was fully hit.
+pub(crate) fn record_sunk(
+    sunk_ships: &mut u8,
+    sunk_cells: &mut [u8; MAX_SHIPS],
+    fleet_size: u8,
+    ship_id: u8,
+    cell: u8,
+) -> Result<()> {
+    require!(ship_id < fleet_size, FleetWarsError::InvalidShipId);
+    let ship_bit = 1u8 << ship_id;
+    require!(*sunk_ships & ship_bit == 0, FleetWarsError::ShipAlreadySunk);
+    *sunk_ships |= ship_bit;
+    sunk_cells[ship_id as usize] = cell;
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct RespondShot<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
     #[account(mut)]
     pub player: Signer<'info>,
+    /// Optional — when present, `player` is this session's key acting for its authority
+    pub session: Option<Account<'info, SessionToken>>,
+    /// CHECK: MagicBlock magic context
+    #[account(mut, address = ephemeral_rollups_sdk::consts::MAGIC_CONTEXT_ID)]
+    pub magic_context: AccountInfo<'info>,
+    /// CHECK: MagicBlock magic program
+    #[account(address = ephemeral_rollups_sdk::consts::MAGIC_PROGRAM_ID)]
+    pub magic_program: AccountInfo<'info>,
 }
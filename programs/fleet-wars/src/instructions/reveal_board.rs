@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use sha2::{Digest, Sha256};
-use crate::state::{Game, game_state, TOTAL_SHIP_CELLS};
+use crate::state::{Game, commitment_mode, game_state};
 use crate::error::FleetWarsError;
+use crate::merkle;
 
-pub fn handler(ctx: Context<RevealBoard>, board: u64, salt: [u8; 32]) -> Result<()> {
+pub fn handler(ctx: Context<RevealBoard>, board: u128, salt: [u8; 32]) -> Result<()> {
     let game = &mut ctx.accounts.game;
 
     require!(
@@ -11,21 +12,21 @@ pub fn handler(ctx: Context<RevealBoard>, board: u64, salt: [u8; 32]) -> Result<
         FleetWarsError::InvalidGameState
     );
 
-    // Validate board has exactly TOTAL_SHIP_CELLS bits set
-    require!(
-        board.count_ones() == TOTAL_SHIP_CELLS as u32,
-        FleetWarsError::InvalidBoard
-    );
-
+    // Fleet shape is not checked here: any board matching the commitment is recorded,
+    // and finalize treats an illegal fleet as cheating.
     let player = ctx.accounts.player.key();
-    let board_bytes = board.to_le_bytes();
-    let mut hasher = Sha256::new();
-    hasher.update(board_bytes);
-    hasher.update(salt);
-    let computed_hash: [u8; 32] = hasher.finalize().into();
+    let computed_hash: [u8; 32] = if game.commitment_mode == commitment_mode::MERKLE {
+        merkle::board_root(board, &salt)
+    } else {
+        let board_bytes = board.to_le_bytes();
+        let mut hasher = Sha256::new();
+        hasher.update(board_bytes);
+        hasher.update(salt);
+        hasher.finalize().into()
+    };
 
     if player == game.player1 {
-        require!(!game.p1_revealed, FleetWarsError::InvalidGameState);
+        require!(!game.p1_revealed && !game.p1_conceded, FleetWarsError::InvalidGameState);
         require!(
             computed_hash == game.p1_board_hash,
             FleetWarsError::BoardHashMismatch
@@ -34,7 +35,7 @@ pub fn handler(ctx: Context<RevealBoard>, board: u64, salt: [u8; 32]) -> Result<
         game.p1_revealed = true;
         msg!("Player1 revealed board");
     } else if player == game.player2 {
-        require!(!game.p2_revealed, FleetWarsError::InvalidGameState);
+        require!(!game.p2_revealed && !game.p2_conceded, FleetWarsError::InvalidGameState);
         require!(
             computed_hash == game.p2_board_hash,
             FleetWarsError::BoardHashMismatch
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::{SessionToken, SESSION_SEED};
+use crate::error::FleetWarsError;
+
+/// The player ends a session early and reclaims its rent.
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    msg!(
+        "Session {} revoked by {}",
+        ctx.accounts.session.session_key,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, session.game.as_ref(), authority.key().as_ref()],
+        bump = session.bump,
+        has_one = authority @ FleetWarsError::Unauthorized,
+        close = authority
+    )]
+    pub session: Account<'info, SessionToken>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
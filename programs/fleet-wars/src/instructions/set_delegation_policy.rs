@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, CONFIG_SEED, MAX_ALLOWED_VALIDATORS};
+use crate::error::FleetWarsError;
+
+/// Admin sets the commit frequency bounds for `delegate_game` and the ER validators games
+/// may be delegated to. An empty list allows any validator.
+pub fn handler(
+    ctx: Context<SetDelegationPolicy>,
+    min_commit_frequency_ms: u32,
+    max_commit_frequency_ms: u32,
+    allowed_validators: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        min_commit_frequency_ms > 0
+            && min_commit_frequency_ms <= max_commit_frequency_ms
+            && allowed_validators.len() <= MAX_ALLOWED_VALIDATORS,
+        FleetWarsError::InvalidDelegationPolicy
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.min_commit_frequency_ms = min_commit_frequency_ms;
+    config.max_commit_frequency_ms = max_commit_frequency_ms;
+    config.allowed_validators = allowed_validators;
+
+    msg!(
+        "Delegation policy: commit every {}..={} ms, {} allowed validators",
+        min_commit_frequency_ms,
+        max_commit_frequency_ms,
+        config.allowed_validators.len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDelegationPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ FleetWarsError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
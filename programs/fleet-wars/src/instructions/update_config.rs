@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, CONFIG_SEED, MAX_FEE_BPS};
+use crate::error::FleetWarsError;
+
+/// Admin changes the fee, the treasury authority, or hands over the admin role.
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    new_admin: Pubkey,
+    treasury_authority: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, FleetWarsError::InvalidFee);
+
+    let config = &mut ctx.accounts.config;
+    config.admin = new_admin;
+    config.treasury_authority = treasury_authority;
+    config.fee_bps = fee_bps;
+
+    msg!("Config updated: fee {} bps, treasury authority {}", fee_bps, treasury_authority);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ FleetWarsError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
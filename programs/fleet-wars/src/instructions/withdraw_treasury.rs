@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Config, Treasury, CONFIG_SEED, TREASURY_SEED};
+use crate::error::FleetWarsError;
+
+/// Treasury authority withdraws collected fees. With `treasury_token` it moves tokens
+/// out of that treasury-owned token account, otherwise lamports above the treasury's
+/// rent-exempt minimum.
+pub fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let treasury = &ctx.accounts.treasury;
+
+    match (&ctx.accounts.treasury_token, &ctx.accounts.token_program) {
+        (Some(treasury_token), Some(token_program)) => {
+            require!(treasury_token.owner == treasury.key(), FleetWarsError::Unauthorized);
+            let seeds: &[&[u8]] = &[TREASURY_SEED, &[treasury.bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: treasury_token.to_account_info(),
+                        to: ctx.accounts.destination.to_account_info(),
+                        authority: treasury.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+        (None, _) => {
+            let treasury_info = treasury.to_account_info();
+            let reserved = Rent::get()?.minimum_balance(Treasury::SPACE);
+            let available = treasury_info.lamports().saturating_sub(reserved);
+            require!(amount <= available, FleetWarsError::InsufficientTreasury);
+            **treasury_info.try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.destination.try_borrow_mut_lamports()? += amount;
+        }
+        (Some(_), None) => return Err(FleetWarsError::MissingTokenAccounts.into()),
+    }
+
+    msg!("Treasury withdrawal of {} to {}", amount, ctx.accounts.destination.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = treasury_authority @ FleetWarsError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    pub treasury_authority: Signer<'info>,
+    /// CHECK: Receives the withdrawal — a wallet for lamports or a token account for tokens
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    #[account(mut)]
+    pub treasury_token: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
@@ -3,38 +3,129 @@ use ephemeral_rollups_sdk::anchor::ephemeral;
 
 pub mod state;
 pub mod error;
+pub mod escrow;
+pub mod lobby;
+pub mod session;
+pub mod fleet;
+pub mod merkle;
+pub mod rating;
+pub mod settlement;
 pub mod instructions;
 
 use instructions::*;
+use state::{CellProof, GameRules, ShotResult};
 pub use instructions::{
-    create_game, delegate_game, end_session, finalize, fire_shot, join_game, respond_shot,
-    reveal_board,
+    cancel_game, checkpoint_game, claim_timeout, claim_unrevealed_forfeit, close_game,
+    create_game, create_session, delegate_game, end_session, expire_game, finalize, fire_salvo,
+    fire_shot, forfeit_reveal, init_config, init_lobby_page, join_and_delegate, join_game,
+    reopen_game, respond_salvo, respond_shot, reveal_board, revoke_session,
+    set_delegation_policy, update_config, withdraw_treasury,
 };
 
+mod __client_accounts_cancel_game {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::cancel_game::__client_accounts_cancel_game::*;
+}
+mod __client_accounts_checkpoint_game {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::checkpoint_game::__client_accounts_checkpoint_game::*;
+}
+mod __client_accounts_claim_timeout {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::claim_timeout::__client_accounts_claim_timeout::*;
+}
+mod __client_accounts_claim_unrevealed_forfeit {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::claim_unrevealed_forfeit::__client_accounts_claim_unrevealed_forfeit::*;
+}
+mod __client_accounts_close_game {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::close_game::__client_accounts_close_game::*;
+}
 mod __client_accounts_create_game {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::create_game::__client_accounts_create_game::*;
 }
+mod __client_accounts_create_session {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::create_session::__client_accounts_create_session::*;
+}
 mod __client_accounts_delegate_game {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::delegate_game::__client_accounts_delegate_game::*;
 }
 mod __client_accounts_end_session {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::end_session::__client_accounts_end_session::*;
 }
+mod __client_accounts_expire_game {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::expire_game::__client_accounts_expire_game::*;
+}
 mod __client_accounts_finalize {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::finalize::__client_accounts_finalize::*;
 }
+mod __client_accounts_fire_salvo {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::fire_salvo::__client_accounts_fire_salvo::*;
+}
 mod __client_accounts_fire_shot {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::fire_shot::__client_accounts_fire_shot::*;
 }
+mod __client_accounts_forfeit_reveal {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::forfeit_reveal::__client_accounts_forfeit_reveal::*;
+}
+mod __client_accounts_init_config {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::init_config::__client_accounts_init_config::*;
+}
+mod __client_accounts_init_lobby_page {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::init_lobby_page::__client_accounts_init_lobby_page::*;
+}
+mod __client_accounts_join_and_delegate {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::join_and_delegate::__client_accounts_join_and_delegate::*;
+}
 mod __client_accounts_join_game {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::join_game::__client_accounts_join_game::*;
 }
+mod __client_accounts_reopen_game {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::reopen_game::__client_accounts_reopen_game::*;
+}
+mod __client_accounts_respond_salvo {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::respond_salvo::__client_accounts_respond_salvo::*;
+}
 mod __client_accounts_respond_shot {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::respond_shot::__client_accounts_respond_shot::*;
 }
 mod __client_accounts_reveal_board {
+    #[allow(unused_imports)]
     pub(crate) use crate::instructions::reveal_board::__client_accounts_reveal_board::*;
 }
+mod __client_accounts_revoke_session {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::revoke_session::__client_accounts_revoke_session::*;
+}
+mod __client_accounts_set_delegation_policy {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::set_delegation_policy::__client_accounts_set_delegation_policy::*;
+}
+mod __client_accounts_update_config {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::update_config::__client_accounts_update_config::*;
+}
+mod __client_accounts_withdraw_treasury {
+    #[allow(unused_imports)]
+    pub(crate) use crate::instructions::withdraw_treasury::__client_accounts_withdraw_treasury::*;
+}
 
 declare_id!("DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn");
 
@@ -43,14 +134,24 @@ declare_id!("DiXQ85BSfM9qgPaTv6PAb2GhxRgGhfoarNGyAYJAqdJn");
 pub mod fleet_wars {
     use super::*;
 
-    /// Phase 1 — L1: Player1 creates a game, commits their board hash, deposits wager.
+    /// Phase 1 — L1: Player1 creates a game, commits their board hash, deposits wager,
+    /// and fixes the ruleset: per-move timeout, commitment mode (flat hash or per-cell
+    /// Merkle root), fleet lengths and board dimensions (up to 128 cells, e.g. 10x10).
+    /// The game id is the next value of player1's profile counter. With `invited` set,
+    /// only that player may join.
     pub fn create_game(
         ctx: Context<CreateGame>,
-        game_id: u64,
         board_hash: [u8; 32],
         wager: u64,
+        rules: GameRules,
+        invited: Option<Pubkey>,
     ) -> Result<()> {
-        create_game::handler(ctx, game_id, board_hash, wager)
+        create_game::handler(ctx, board_hash, wager, rules, invited)
+    }
+
+    /// Phase 1 — L1: Anyone opens a new lobby page for a wager bracket.
+    pub fn init_lobby_page(ctx: Context<InitLobbyPage>, bracket: u8, page: u16) -> Result<()> {
+        init_lobby_page::handler(ctx, bracket, page)
     }
 
     /// Phase 1 — L1: Player2 joins, commits their board hash, deposits wager.
@@ -58,34 +159,168 @@ pub mod fleet_wars {
         join_game::handler(ctx, board_hash)
     }
 
-    /// Phase 1 — L1: Player1 delegates the game account to MagicBlock Ephemeral Rollup.
-    pub fn delegate_game(ctx: Context<DelegateGame>, game_id: u64) -> Result<()> {
-        delegate_game::handler(ctx, game_id)
+    /// Phase 1 — L1: Player2 joins and delegates the game to the ER in one transaction.
+    pub fn join_and_delegate(
+        ctx: Context<JoinAndDelegate>,
+        board_hash: [u8; 32],
+        commit_frequency_ms: u32,
+    ) -> Result<()> {
+        join_and_delegate::handler(ctx, board_hash, commit_frequency_ms)
+    }
+
+    /// Phase 1 — L1: Player1 drops the invite so anyone may join the game, optionally listing
+    /// it in a lobby page.
+    pub fn reopen_game(ctx: Context<ReopenGame>) -> Result<()> {
+        reopen_game::handler(ctx)
+    }
+
+    /// Phase 1 — L1: Player1 cancels an unjoined game; wager and rent are refunded.
+    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+        cancel_game::handler(ctx)
+    }
+
+    /// Phase 1 — L1: Anyone refunds a stale unjoined game to its creator after the lobby expiry.
+    pub fn expire_game(ctx: Context<ExpireGame>) -> Result<()> {
+        expire_game::handler(ctx)
+    }
+
+    /// Phase 1 — L1: Either player delegates the game account to MagicBlock Ephemeral Rollup.
+    /// The commit frequency and validator must satisfy the config's delegation policy.
+    pub fn delegate_game(
+        ctx: Context<DelegateGame>,
+        game_id: u64,
+        commit_frequency_ms: u32,
+    ) -> Result<()> {
+        delegate_game::handler(ctx, game_id, commit_frequency_ms)
+    }
+
+    /// Phase 1 — L1: A player authorizes an ephemeral session key to sign their ER moves in
+    /// one game, with an expiry and a capped fee top-up.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        lamports: u64,
+    ) -> Result<()> {
+        create_session::handler(ctx, session_key, expires_at, lamports)
+    }
+
+    /// Phase 1 — L1: A player revokes their session key for a game.
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        revoke_session::handler(ctx)
     }
 
-    /// Phase 2 — ER: Active player fires a shot at a cell (0–63).
+    /// Phase 2 — ER: Active player fires a shot at a cell (0..rows * cols).
     pub fn fire_shot(ctx: Context<FireShot>, cell: u8) -> Result<()> {
         fire_shot::handler(ctx, cell)
     }
 
-    /// Phase 2 — ER: Opponent declares hit or miss for the last shot.
-    /// Auto-undelegates back to L1 when all ships are sunk.
-    pub fn respond_shot(ctx: Context<RespondShot>, hit: bool) -> Result<()> {
-        respond_shot::handler(ctx, hit)
+    /// Phase 2 — ER: Opponent declares miss, hit or sunk(ship_id) for the last shot.
+    /// In Merkle mode the answer must carry an inclusion proof for the shot cell.
+    /// The answer that sinks the last ship commits and undelegates the game back to L1.
+    pub fn respond_shot(
+        ctx: Context<RespondShot>,
+        result: ShotResult,
+        proof: Option<CellProof>,
+    ) -> Result<()> {
+        respond_shot::handler(ctx, result, proof)
+    }
+
+    /// Phase 2 — ER: Salvo mode — active player fires one shot per ship still afloat.
+    pub fn fire_salvo(ctx: Context<FireSalvo>, cells: Vec<u8>) -> Result<()> {
+        fire_salvo::handler(ctx, cells)
+    }
+
+    /// Phase 2 — ER: Salvo mode — opponent answers every shot of the pending salvo,
+    /// in ascending cell order. Undelegates the game when the salvo ends it.
+    pub fn respond_salvo(ctx: Context<RespondSalvo>, results: Vec<ShotResult>) -> Result<()> {
+        respond_salvo::handler(ctx, results)
+    }
+
+    /// Phase 2 — ER: Either player commits the current game state to L1 while staying
+    /// delegated.
+    pub fn checkpoint_game(ctx: Context<CheckpointGame>) -> Result<()> {
+        checkpoint_game::handler(ctx)
+    }
+
+    /// Phase 2 — ER: Waiting player claims the win after the opponent misses their turn deadline,
+    /// undelegating the game back to L1.
+    pub fn claim_timeout(ctx: Context<ClaimTimeout>) -> Result<()> {
+        claim_timeout::handler(ctx)
     }
 
-    /// Phase 2 — ER: Manually end the ER session (e.g. opponent abandoned).
+    /// Phase 2 — ER: Manually end the ER session (e.g. opponent abandoned). A game still in
+    /// battle moves to the reveal phase; the caller forfeits unless the opponent timed out.
     pub fn end_session(ctx: Context<EndSession>) -> Result<()> {
         end_session::handler(ctx)
     }
 
     /// Phase 3 — L1: Each player reveals their actual board + salt to prove honesty.
-    pub fn reveal_board(ctx: Context<RevealBoard>, board: u64, salt: [u8; 32]) -> Result<()> {
+    pub fn reveal_board(ctx: Context<RevealBoard>, board: u128, salt: [u8; 32]) -> Result<()> {
         reveal_board::handler(ctx, board, salt)
     }
 
+    /// Phase 3 — L1: A player who cannot produce a matching reveal concedes, awarding
+    /// the opponent and letting finalize proceed without that board.
+    pub fn forfeit_reveal(ctx: Context<ForfeitReveal>) -> Result<()> {
+        forfeit_reveal::handler(ctx)
+    }
+
     /// Phase 3 — L1: Verifies both reveals, detects cheaters, pays winner.
+    /// Closes the game to player1 when the optional `player1` account is supplied.
     pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
         finalize::handler(ctx)
     }
+
+    /// Phase 3 — L1: After the reveal window closes, marks every non-revealer as a cheater.
+    /// Pays the full pot to a lone revealer whose board passes the finalize checks, otherwise
+    /// voids the game.
+    pub fn claim_unrevealed_forfeit(ctx: Context<ClaimUnrevealedForfeit>) -> Result<()> {
+        claim_unrevealed_forfeit::handler(ctx)
+    }
+
+    /// Phase 3 — L1: Closes a finished game and returns its rent to player1.
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        close_game::handler(ctx)
+    }
+
+    /// Admin — L1: Upgrade authority creates the protocol config and fee treasury.
+    pub fn init_config(
+        ctx: Context<InitConfig>,
+        treasury_authority: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        init_config::handler(ctx, treasury_authority, fee_bps)
+    }
+
+    /// Admin — L1: Config admin updates the fee, treasury authority or admin.
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        new_admin: Pubkey,
+        treasury_authority: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        update_config::handler(ctx, new_admin, treasury_authority, fee_bps)
+    }
+
+    /// Admin — L1: Config admin sets delegation commit frequency bounds and the ER
+    /// validator allowlist.
+    pub fn set_delegation_policy(
+        ctx: Context<SetDelegationPolicy>,
+        min_commit_frequency_ms: u32,
+        max_commit_frequency_ms: u32,
+        allowed_validators: Vec<Pubkey>,
+    ) -> Result<()> {
+        set_delegation_policy::handler(
+            ctx,
+            min_commit_frequency_ms,
+            max_commit_frequency_ms,
+            allowed_validators,
+        )
+    }
+
+    /// Admin — L1: Treasury authority withdraws collected fees (lamports or tokens).
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        withdraw_treasury::handler(ctx, amount)
+    }
 }
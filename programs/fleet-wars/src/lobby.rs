@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{Game, LobbyEntry, LobbyPage, LOBBY_PAGE_SIZE};
+use crate::error::FleetWarsError;
+
+/// Lists an open game in `page`, which must be a page of the game's wager bracket with
+/// room left.
+pub fn register(page: &mut Account<LobbyPage>, game_key: Pubkey, game: &Game) -> Result<()> {
+    require!(
+        page.bracket == LobbyPage::bracket_for(game.wager),
+        FleetWarsError::InvalidLobby
+    );
+    require!(page.entries.len() < LOBBY_PAGE_SIZE, FleetWarsError::LobbyFull);
+
+    page.entries.push(LobbyEntry {
+        game: game_key,
+        mint: game.mint,
+        wager: game.wager,
+        game_mode: game.game_mode,
+        commitment_mode: game.commitment_mode,
+        board_rows: game.board_rows,
+        board_cols: game.board_cols,
+        rated: game.rated,
+        bond: game.bond,
+        fleet: game.fleet,
+        fleet_size: game.fleet_size,
+        turn_timeout_secs: game.turn_timeout_secs,
+    });
+    Ok(())
+}
+
+/// Drops a game that is leaving the lobby (joined, cancelled or expired). Listed games
+/// must pass the page recorded in `game.lobby`; unlisted games need no page.
+pub fn unregister(
+    page: Option<&mut Account<LobbyPage>>,
+    game_key: Pubkey,
+    game: &Game,
+) -> Result<()> {
+    if game.lobby == Pubkey::default() {
+        return Ok(());
+    }
+
+    let page = page.ok_or(FleetWarsError::InvalidLobby)?;
+    require!(page.key() == game.lobby, FleetWarsError::InvalidLobby);
+    page.entries.retain(|entry| entry.game != game_key);
+    Ok(())
+}
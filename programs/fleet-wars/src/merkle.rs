@@ -0,0 +1,110 @@
+use solana_sha256_hasher::hashv;
+
+/// Depth of the per-cell commitment tree: 2^7 = 128 leaves, one per bitboard cell.
+/// Cells beyond the game's rows x cols are committed as unoccupied.
+pub const MERKLE_DEPTH: usize = 7;
+pub const MERKLE_LEAVES: usize = 1 << MERKLE_DEPTH;
+
+/// Per-cell salt derived from the player's master salt: SHA256(salt || cell).
+/// Revealing one cell's salt in `respond_shot` says nothing about the others.
+///
+/// All hashing here goes through the `sol_sha256` syscall: rebuilding the root at reveal
+/// takes ~380 hashes, which software SHA-256 could not fit in an instruction's compute.
+pub fn cell_salt(salt: &[u8; 32], cell: u8) -> [u8; 32] {
+    hashv(&[salt.as_ref(), &[cell]]).to_bytes()
+}
+
+/// Leaf = SHA256(cell || occupied || cell_salt).
+pub fn leaf(cell: u8, occupied: bool, cell_salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[cell, occupied as u8], cell_salt.as_ref()]).to_bytes()
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+/// Root over all 128 salted cell leaves of `board`, rebuilt at reveal time.
+pub fn board_root(board: u128, salt: &[u8; 32]) -> [u8; 32] {
+    let mut level = [[0u8; 32]; MERKLE_LEAVES];
+    for (cell, slot) in level.iter_mut().enumerate() {
+        let cell = cell as u8;
+        *slot = leaf(cell, board & (1u128 << cell) != 0, &cell_salt(salt, cell));
+    }
+
+    let mut width = MERKLE_LEAVES;
+    while width > 1 {
+        for i in 0..width / 2 {
+            level[i] = node(&level[2 * i], &level[2 * i + 1]);
+        }
+        width /= 2;
+    }
+    level[0]
+}
+
+/// Checks that `cell` is committed as `occupied` under `root`. `siblings` are ordered
+/// from the leaf level upwards.
+pub fn verify_cell(
+    root: &[u8; 32],
+    cell: u8,
+    occupied: bool,
+    cell_salt: &[u8; 32],
+    siblings: &[[u8; 32]; MERKLE_DEPTH],
+) -> bool {
+    let mut hash = leaf(cell, occupied, cell_salt);
+    let mut index = cell as usize;
+    for sibling in siblings {
+        hash = if index & 1 == 0 {
+            node(&hash, sibling)
+        } else {
+            node(sibling, &hash)
+        };
+        index >>= 1;
+    }
+    hash == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sibling path for `cell`, built the same way clients do off-chain.
+    fn proof(board: u128, salt: &[u8; 32], cell: u8) -> [[u8; 32]; MERKLE_DEPTH] {
+        let mut level: Vec<[u8; 32]> = (0..MERKLE_LEAVES as u8)
+            .map(|c| leaf(c, board & (1u128 << c) != 0, &cell_salt(salt, c)))
+            .collect();
+        let mut siblings = [[0u8; 32]; MERKLE_DEPTH];
+        let mut index = cell as usize;
+        for sibling in siblings.iter_mut() {
+            *sibling = level[index ^ 1];
+            level = level.chunks(2).map(|pair| node(&pair[0], &pair[1])).collect();
+            index >>= 1;
+        }
+        siblings
+    }
+
+    #[test]
+    fn verify_cell_matches_board_root() {
+        let salt = [7u8; 32];
+        let board: u128 = 0b1110 | (1 << 63) | (1 << 64) | (1 << 127);
+        let root = board_root(board, &salt);
+
+        for cell in [0u8, 1, 3, 63, 64, 100, 127] {
+            let occupied = board & (1u128 << cell) != 0;
+            let siblings = proof(board, &salt, cell);
+            let cell_salt = cell_salt(&salt, cell);
+            assert!(verify_cell(&root, cell, occupied, &cell_salt, &siblings));
+            assert!(!verify_cell(&root, cell, !occupied, &cell_salt, &siblings));
+        }
+    }
+
+    #[test]
+    fn verify_cell_rejects_wrong_salt_or_cell() {
+        let salt = [7u8; 32];
+        let board: u128 = 0b1110;
+        let root = board_root(board, &salt);
+        let siblings = proof(board, &salt, 1);
+
+        assert!(!verify_cell(&root, 1, true, &cell_salt(&[8u8; 32], 1), &siblings));
+        assert!(!verify_cell(&root, 2, true, &cell_salt(&salt, 2), &siblings));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::state::{Game, game_state};
+
+pub const INITIAL_RATING: u32 = 1200;
+pub const MIN_RATING: u32 = 100;
+pub const K_FACTOR: i64 = 32;
+pub const CHEAT_PENALTY: i64 = 50;
+
+/// Expected score of the higher-rated player in per-mille, 1 / (1 + 10^(-gap / 400)),
+/// indexed by rating gap / 25. Gaps beyond 400 use the last entry.
+const EXPECTED_MILLI: [i64; 17] = [
+    500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909,
+];
+
+/// Expected score of `rating` against `opponent`, in per-mille.
+pub fn expected_milli(rating: u32, opponent: u32) -> i64 {
+    let gap = (rating as i64 - opponent as i64).clamp(-400, 400);
+    let expected = EXPECTED_MILLI[(gap.unsigned_abs() / 25) as usize];
+    if gap >= 0 {
+        expected
+    } else {
+        1000 - expected
+    }
+}
+
+fn adjust(rating: u32, delta: i64) -> u32 {
+    (rating as i64 + delta).max(MIN_RATING as i64) as u32
+}
+
+/// Applies a settled rated game to both players' ratings. Wins, losses and draws move
+/// ratings by Elo, zero-sum between the two players; each cheater then loses
+/// `CHEAT_PENALTY` on top. A voided game (both cheated) has no result, so only the
+/// penalties apply.
+pub fn settle(p1_rating: &mut u32, p2_rating: &mut u32, game: &Game) {
+    if game.game_state != game_state::VOIDED {
+        let p1_score = match (game.game_state, game.winner) {
+            (game_state::FINISHED, 1) => 1000,
+            (game_state::FINISHED, _) => 0,
+            _ => 500,
+        };
+        let delta = K_FACTOR * (p1_score - expected_milli(*p1_rating, *p2_rating)) / 1000;
+        // Zero-sum: the gainer only takes what the other player can lose above the floor.
+        let (p1, p2) = (*p1_rating as i64, *p2_rating as i64);
+        let delta = if delta > 0 {
+            delta.min(p2 - MIN_RATING as i64)
+        } else {
+            delta.max(MIN_RATING as i64 - p1)
+        };
+        (*p1_rating, *p2_rating) = ((p1 + delta) as u32, (p2 - delta) as u32);
+    }
+    if game.p1_cheated {
+        *p1_rating = adjust(*p1_rating, -CHEAT_PENALTY);
+    }
+    if game.p2_cheated {
+        *p2_rating = adjust(*p2_rating, -CHEAT_PENALTY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+
+    fn game(state: u8, winner: u8, p1_cheated: bool, p2_cheated: bool) -> Game {
+        let mut game = Game::deserialize(&mut &[0u8; Game::SPACE - 8][..]).unwrap();
+        game.game_state = state;
+        game.winner = winner;
+        game.p1_cheated = p1_cheated;
+        game.p2_cheated = p2_cheated;
+        game
+    }
+
+    fn settled(p1: u32, p2: u32, game: &Game) -> (u32, u32) {
+        let (mut p1, mut p2) = (p1, p2);
+        settle(&mut p1, &mut p2, game);
+        (p1, p2)
+    }
+
+    #[test]
+    fn expected_score_table() {
+        assert_eq!(expected_milli(1200, 1200), 500);
+        assert_eq!(expected_milli(1400, 1200), 760);
+        assert_eq!(expected_milli(1200, 1400), 240);
+        assert_eq!(expected_milli(2000, 1200), 909);
+        assert_eq!(expected_milli(1200, 2000), 91);
+    }
+
+    #[test]
+    fn equal_ratings_win() {
+        let won = game(game_state::FINISHED, 1, false, false);
+        assert_eq!(settled(1200, 1200, &won), (1216, 1184));
+        let lost = game(game_state::FINISHED, 2, false, false);
+        assert_eq!(settled(1200, 1200, &lost), (1184, 1216));
+    }
+
+    #[test]
+    fn draw_moves_toward_the_lower_rating() {
+        let drawn = game(game_state::DRAWN, 0, false, false);
+        assert_eq!(settled(1200, 1200, &drawn), (1200, 1200));
+        assert_eq!(settled(1400, 1200, &drawn), (1392, 1208));
+    }
+
+    #[test]
+    fn gap_beyond_400_is_clamped() {
+        let favourite_won = game(game_state::FINISHED, 1, false, false);
+        assert_eq!(settled(1800, 1200, &favourite_won), (1802, 1198));
+        let upset = game(game_state::FINISHED, 2, false, false);
+        assert_eq!(settled(1800, 1200, &upset), (1771, 1229));
+    }
+
+    #[test]
+    fn floor_is_zero_sum() {
+        // The winner only gains the points the loser actually lost above the floor.
+        let won = game(game_state::FINISHED, 2, false, false);
+        assert_eq!(settled(MIN_RATING, MIN_RATING, &won), (MIN_RATING, MIN_RATING));
+        assert_eq!(settled(MIN_RATING + 5, MIN_RATING + 5, &won), (MIN_RATING, MIN_RATING + 10));
+    }
+
+    #[test]
+    fn cheat_penalty_stops_at_the_floor() {
+        let caught = game(game_state::FINISHED, 2, true, false);
+        assert_eq!(settled(120, 1200, &caught), (MIN_RATING, 1202));
+        assert_eq!(settled(1200, 1200, &caught), (1134, 1216));
+    }
+
+    #[test]
+    fn voided_game_applies_only_penalties() {
+        let voided = game(game_state::VOIDED, 0, true, true);
+        assert_eq!(settled(MIN_RATING, 1200, &voided), (MIN_RATING, 1150));
+    }
+}
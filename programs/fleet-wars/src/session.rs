@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::SessionToken;
+use crate::error::FleetWarsError;
+
+/// Resolves who is acting in `game`. Without a session the signer acts for themselves;
+/// with one, the signer must be that session's unexpired key for this game and acts for
+/// the session's authority.
+pub fn acting_player(
+    signer: &Signer,
+    session: Option<&Account<SessionToken>>,
+    game: Pubkey,
+) -> Result<Pubkey> {
+    let Some(session) = session else {
+        return Ok(signer.key());
+    };
+
+    require!(
+        session.game == game && session.session_key == signer.key(),
+        FleetWarsError::InvalidSession
+    );
+    require!(
+        Clock::get()?.unix_timestamp < session.expires_at,
+        FleetWarsError::SessionExpired
+    );
+    Ok(session.authority)
+}
@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use crate::error::FleetWarsError;
+use crate::fleet;
+use crate::state::{Config, Game, game_state};
+
+/// Whether `player` (1 or 2) is treated as a cheater at settlement: they have no revealed
+/// board (they conceded or never revealed), or the revealed board contradicts what they
+/// declared during the battle.
+pub fn cheated(game: &Game, player: u8) -> bool {
+    let (revealed, board, declared, opponent_shots, sunk_ships, sunk_cells) = if player == 1 {
+        (
+            game.p1_revealed,
+            game.p1_board,
+            game.p1_declared_hits,
+            game.p2_shots,
+            game.p1_sunk_ships,
+            &game.p1_sunk_cells,
+        )
+    } else {
+        (
+            game.p2_revealed,
+            game.p2_board,
+            game.p2_declared_hits,
+            game.p1_shots,
+            game.p2_sunk_ships,
+            &game.p2_sunk_cells,
+        )
+    };
+    if !revealed {
+        return true;
+    }
+
+    // Declared hits must be exactly the opponent's shots that landed on the board. Salvo
+    // games record shots and answers in the same bitmasks, so this covers both modes.
+    // The board must also be a legal fleet, and every sunk announcement must match a ship
+    // the opponent fully hit.
+    declared != opponent_shots & board
+        || !fleet::matches_sunk_declarations(
+            board,
+            game.board_rows,
+            game.board_cols,
+            game.ship_lengths(),
+            opponent_shots,
+            sunk_ships,
+            sunk_cells,
+        )
+}
+
+/// Records the cheat flags and applies the settlement table:
+///   only P2 cheated           -> FINISHED, P1 wins
+///   only P1 cheated           -> FINISHED, P2 wins
+///   nobody cheated, winner    -> FINISHED, declared winner from the ER wins
+///   nobody cheated, no winner -> DRAWN
+///   both cheated              -> VOIDED
+pub fn decide(game: &mut Game, p1_cheated: bool, p2_cheated: bool) {
+    game.winner = match (p1_cheated, p2_cheated) {
+        (false, true) => 1,
+        (true, false) => 2,
+        (true, true) => 0,
+        (false, false) => game.winner,
+    };
+    game.p1_cheated = p1_cheated;
+    game.p2_cheated = p2_cheated;
+    game.game_state = match (game.winner, p1_cheated && p2_cheated) {
+        (_, true) => game_state::VOIDED,
+        (0, false) => game_state::DRAWN,
+        _ => game_state::FINISHED,
+    };
+}
+
+/// Amounts owed to (player1, player2, treasury) for a decided game. A draw refunds each
+/// stake and a voided game slashes both stakes to the treasury. Otherwise the winner takes
+/// the pot less the protocol fee, plus their own bond; an honest loser gets their bond back
+/// and a cheating loser's bond also goes to the winner. The amounts always add up to both
+/// stakes, so the escrow is left empty.
+pub fn split(game: &Game, config: &Config) -> Result<(u64, u64, u64)> {
+    let total_pot = game.wager.checked_mul(2).ok_or(FleetWarsError::SettlementOverflow)?;
+    let bonds = game.bond.checked_mul(2).ok_or(FleetWarsError::SettlementOverflow)?;
+    let both_stakes = total_pot.checked_add(bonds).ok_or(FleetWarsError::SettlementOverflow)?;
+
+    match game.game_state {
+        game_state::DRAWN => Ok((game.stake(), game.stake(), 0)),
+        game_state::VOIDED => Ok((0, 0, both_stakes)),
+        game_state::FINISHED => {
+            let fee = config.fee_for(total_pot);
+            let loser_cheated = if game.winner == 1 { game.p2_cheated } else { game.p1_cheated };
+            let (winner_bond, loser_paid) = if loser_cheated {
+                (bonds, 0)
+            } else {
+                (game.bond, game.bond)
+            };
+            let winner_paid = total_pot
+                .checked_sub(fee)
+                .and_then(|prize| prize.checked_add(winner_bond))
+                .ok_or(FleetWarsError::SettlementOverflow)?;
+            if game.winner == 1 {
+                Ok((winner_paid, loser_paid, fee))
+            } else {
+                Ok((loser_paid, winner_paid, fee))
+            }
+        }
+        _ => Err(FleetWarsError::InvalidGameState.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+
+    const WAGER: u64 = 1_000_000;
+    const BOND: u64 = 250_000;
+
+    // 2 + 3 + 4 fleet on an 8x8 board: A1-A2, B1-B3, C1-C4.
+    const BOARD: u128 = 0b11 | 0b111 << 8 | 0b1111 << 16;
+
+    fn game(winner: u8) -> Game {
+        let mut game = Game::deserialize(&mut &[0u8; Game::SPACE - 8][..]).unwrap();
+        game.wager = WAGER;
+        game.bond = BOND;
+        game.winner = winner;
+        game.fleet[..3].copy_from_slice(&[2, 3, 4]);
+        game.fleet_size = 3;
+        game.board_rows = 8;
+        game.board_cols = 8;
+        game.p1_board = BOARD;
+        game.p2_board = BOARD;
+        game.p1_revealed = true;
+        game.p2_revealed = true;
+        game
+    }
+
+    fn config(fee_bps: u16) -> Config {
+        Config {
+            admin: Pubkey::default(),
+            treasury_authority: Pubkey::default(),
+            fee_bps,
+            min_commit_frequency_ms: 0,
+            max_commit_frequency_ms: 0,
+            allowed_validators: vec![],
+            bump: 0,
+        }
+    }
+
+    fn settled(winner: u8, p1_cheated: bool, p2_cheated: bool) -> Game {
+        let mut game = game(winner);
+        decide(&mut game, p1_cheated, p2_cheated);
+        game
+    }
+
+    #[test]
+    fn outcome_table() {
+        let cases = [
+            // (declared winner, p1 cheated, p2 cheated) -> (state, winner)
+            ((2, false, true), (game_state::FINISHED, 1)),
+            ((1, true, false), (game_state::FINISHED, 2)),
+            ((2, false, false), (game_state::FINISHED, 2)),
+            ((0, false, false), (game_state::DRAWN, 0)),
+            ((1, true, true), (game_state::VOIDED, 0)),
+        ];
+        for ((winner, p1_cheated, p2_cheated), expected) in cases {
+            let game = settled(winner, p1_cheated, p2_cheated);
+            assert_eq!((game.game_state, game.winner), expected);
+            assert_eq!((game.p1_cheated, game.p2_cheated), (p1_cheated, p2_cheated));
+        }
+    }
+
+    #[test]
+    fn honest_revealed_board_is_not_cheating() {
+        let mut game = game(1);
+        // P1 hit all of P2's ships and P2 declared every hit and sinking honestly.
+        game.p1_shots = BOARD;
+        game.p2_declared_hits = BOARD;
+        game.p2_sunk_ships = 0b111;
+        game.p2_sunk_cells[..3].copy_from_slice(&[1, 10, 19]);
+        assert!(!cheated(&game, 1));
+        assert!(!cheated(&game, 2));
+    }
+
+    #[test]
+    fn lies_and_concessions_are_cheating() {
+        let mut lied = game(1);
+        lied.p1_shots = 0b1;
+        assert!(cheated(&lied, 2), "undeclared hit");
+
+        let mut conceded = game(1);
+        conceded.p2_revealed = false;
+        conceded.p2_conceded = true;
+        assert!(cheated(&conceded, 2));
+
+        let mut bad_fleet = game(1);
+        bad_fleet.p1_board = BOARD | 1 << 63;
+        assert!(cheated(&bad_fleet, 1), "extra ship cell");
+    }
+
+    #[test]
+    fn honest_loser_keeps_bond_and_fee_goes_to_treasury() {
+        // 2.5% of the 2_000_000 pot.
+        let (p1, p2, treasury) = split(&settled(1, false, false), &config(250)).unwrap();
+        assert_eq!((p1, p2, treasury), (2 * WAGER - 50_000 + BOND, BOND, 50_000));
+
+        let (p1, p2, treasury) = split(&settled(2, false, false), &config(250)).unwrap();
+        assert_eq!((p1, p2, treasury), (BOND, 2 * WAGER - 50_000 + BOND, 50_000));
+    }
+
+    #[test]
+    fn cheater_forfeits_bond_to_the_winner() {
+        let (p1, p2, treasury) = split(&settled(1, true, false), &config(0)).unwrap();
+        assert_eq!((p1, p2, treasury), (0, 2 * WAGER + 2 * BOND, 0));
+    }
+
+    #[test]
+    fn draw_refunds_and_void_slashes() {
+        let stake = WAGER + BOND;
+        assert_eq!(split(&settled(0, false, false), &config(250)).unwrap(), (stake, stake, 0));
+        assert_eq!(split(&settled(2, true, true), &config(250)).unwrap(), (0, 0, 2 * stake));
+    }
+
+    #[test]
+    fn every_outcome_drains_the_escrow() {
+        let flags = [(false, false), (true, false), (false, true), (true, true)];
+        for winner in 0..=2 {
+            for (p1_cheated, p2_cheated) in flags {
+                let game = settled(winner, p1_cheated, p2_cheated);
+                let (p1, p2, treasury) = split(&game, &config(1_000)).unwrap();
+                assert_eq!(p1 + p2 + treasury, 2 * (WAGER + BOND));
+            }
+        }
+    }
+
+    #[test]
+    fn unsettled_game_is_rejected() {
+        let mut game = game(1);
+        game.game_state = game_state::WAITING_REVEAL;
+        assert!(split(&game, &config(0)).is_err());
+    }
+}
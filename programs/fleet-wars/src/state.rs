@@ -1,7 +1,32 @@
 use anchor_lang::prelude::*;
+use crate::merkle::MERKLE_DEPTH;
+use crate::rating::{self, INITIAL_RATING};
 
-pub const TOTAL_SHIP_CELLS: u8 = 9; // ships: 2 + 3 + 4 cells
+pub const MAX_SHIPS: usize = 5;
+pub const MIN_SHIP_LEN: u8 = 2;
+pub const MAX_SHIP_LEN: u8 = 5;
+pub const MIN_BOARD_DIM: u8 = 5;
+pub const MAX_BOARD_DIM: u8 = 16;
+pub const MAX_BOARD_CELLS: u16 = 128; // bitboards are u128
 pub const GAME_SEED: &[u8] = b"game";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const PROFILE_SEED: &[u8] = b"profile";
+pub const LOBBY_SEED: &[u8] = b"lobby";
+pub const LOBBY_PAGE_SIZE: usize = 32;
+pub const MAX_WAGER_BRACKET: u8 = 20; // one bracket per decimal digit of a u64 wager
+pub const MAX_FEE_BPS: u16 = 1_000; // 10%
+pub const MAX_ALLOWED_VALIDATORS: usize = 8;
+pub const DEFAULT_MIN_COMMIT_FREQUENCY_MS: u32 = 1_000;
+pub const DEFAULT_MAX_COMMIT_FREQUENCY_MS: u32 = 60_000;
+pub const MIN_TURN_TIMEOUT_SECS: i64 = 30;
+pub const MAX_TURN_TIMEOUT_SECS: i64 = 7 * 24 * 60 * 60;
+pub const LOBBY_EXPIRY_SECS: i64 = 24 * 60 * 60;
+pub const MAX_SESSION_SECS: i64 = 24 * 60 * 60;
+pub const MAX_SESSION_LAMPORTS: u64 = 10_000_000; // 0.01 SOL of fees for the session key
+pub const REVEAL_WINDOW_SECS: i64 = 24 * 60 * 60;
 
 pub mod game_state {
     pub const WAITING_FOR_PLAYER: u8 = 0;
@@ -9,6 +34,10 @@ pub mod game_state {
     pub const ACTIVE: u8 = 2;
     pub const WAITING_REVEAL: u8 = 3;
     pub const FINISHED: u8 = 4;
+    /// Settled with no winner: both wagers refunded.
+    pub const DRAWN: u8 = 5;
+    /// Settled with both players caught cheating: the pot is slashed to the treasury.
+    pub const VOIDED: u8 = 6;
 }
 
 pub mod turn_state {
@@ -18,40 +47,417 @@ pub mod turn_state {
     pub const P1_RESPOND: u8 = 4;
 }
 
+pub mod commitment_mode {
+    /// Board hash is SHA256(board_u128_le || salt); lies are caught at finalize.
+    pub const HASH: u8 = 0;
+    /// Board hash is a Merkle root over 128 salted cell leaves; every answer is proven.
+    pub const MERKLE: u8 = 1;
+}
+
+pub mod game_mode {
+    /// One shot per turn via fire_shot / respond_shot.
+    pub const CLASSIC: u8 = 0;
+    /// One shot per ship still afloat per turn via fire_salvo / respond_salvo.
+    pub const SALVO: u8 = 1;
+}
+
+/// Ruleset chosen by player1 at `create_game` and fixed for the rest of the game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GameRules {
+    pub turn_timeout_secs: i64,
+    pub commitment_mode: u8,
+    /// Ship lengths, one entry per ship (1–5 ships of 2–5 cells).
+    pub fleet: Vec<u8>,
+    pub board_rows: u8,
+    pub board_cols: u8,
+    pub game_mode: u8,
+    /// Honesty bond each player posts on top of the wager; 0 for none.
+    pub bond: u64,
+    /// Whether settlement updates the players' Elo ratings.
+    pub rated: bool,
+}
+
+/// Inclusion proof for the shot cell, supplied with `respond_shot` in Merkle mode.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CellProof {
+    pub cell_salt: [u8; 32],
+    pub siblings: [[u8; 32]; MERKLE_DEPTH],
+}
+
+/// Responder's answer to the last shot. `ship_id` indexes the game's fleet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ShotResult {
+    Miss,
+    Hit,
+    Sunk { ship_id: u8 },
+}
+
 #[account]
 pub struct Game {
     pub player1: Pubkey,           // 32
     pub player2: Pubkey,           // 32
-    pub p1_board_hash: [u8; 32],   // 32 — SHA256(board_u64_le || salt)
+    pub invited: Pubkey,           // 32 — only this player may join; default = open game
+    pub lobby: Pubkey,             // 32 — lobby page listing this game; default = unlisted
+    pub p1_board_hash: [u8; 32],   // 32 — SHA256(board_u128_le || salt) or Merkle root
     pub p2_board_hash: [u8; 32],   // 32
+    pub mint: Pubkey,              // 32 — SPL mint of the stakes; default = lamports
 
-    pub p1_shots: u64,             // 8  — cells P1 fired at P2's board
-    pub p2_shots: u64,             // 8  — cells P2 fired at P1's board
-    pub p1_declared_hits: u64,     // 8  — P1 said "hit" on these cells (responding to P2's shots)
-    pub p2_declared_hits: u64,     // 8  — P2 said "hit" on these cells (responding to P1's shots)
-    pub p1_board: u64,             // 8  — revealed after game
-    pub p2_board: u64,             // 8  — revealed after game
+    pub p1_shots: u128,            // 16 — cells P1 fired at P2's board
+    pub p2_shots: u128,            // 16 — cells P2 fired at P1's board
+    pub p1_declared_hits: u128,    // 16 — P1 said "hit" on these cells (responding to P2's shots)
+    pub p2_declared_hits: u128,    // 16 — P2 said "hit" on these cells (responding to P1's shots)
+    pub p1_board: u128,            // 16 — revealed after game
+    pub p2_board: u128,            // 16 — revealed after game
+    pub pending_shots: u128,       // 16 — salvo cells awaiting respond_salvo
+    pub p1_sunk_cells: [u8; MAX_SHIPS], // 5 — cell at which P1 declared each own ship sunk
+    pub p2_sunk_cells: [u8; MAX_SHIPS], // 5
+    pub fleet: [u8; MAX_SHIPS],    // 5  — ship lengths; only the first fleet_size are used
 
     pub wager: u64,                // 8
+    pub bond: u64,                 // 8  — honesty bond per player, forfeited on cheating
     pub game_id: u64,              // 8
+    pub last_action_ts: i64,       // 8  — unix time of the last create/join/fire/respond
+    pub turn_timeout_secs: i64,    // 8  — how long the player on move has before claim_timeout
+    pub reveal_deadline: i64,      // 8  — end of the reveal window, set on entering WAITING_REVEAL
 
     pub p1_hits_on_p2: u8,         // 1  — running declared hits P1 has on P2
     pub p2_hits_on_p1: u8,         // 1  — running declared hits P2 has on P1
-    pub last_shot_cell: u8,        // 1  — last shot cell index (0–63)
+    pub p1_sunk_ships: u8,         // 1  — bitmask of P1's ships P1 declared sunk
+    pub p2_sunk_ships: u8,         // 1  — bitmask of P2's ships P2 declared sunk
+    pub fleet_size: u8,            // 1  — number of ships per player
+    pub total_ship_cells: u8,      // 1  — sum of fleet lengths; hits needed to win
+    pub board_rows: u8,            // 1
+    pub board_cols: u8,            // 1
+    pub last_shot_cell: u8,        // 1  — last shot cell index (row * board_cols + col)
     pub turn_state: u8,            // 1
     pub game_state: u8,            // 1
+    pub commitment_mode: u8,       // 1  — see commitment_mode
+    pub game_mode: u8,             // 1  — see game_mode
+    pub rated: bool,               // 1  — settlement updates Elo ratings
     pub p1_revealed: bool,         // 1
     pub p2_revealed: bool,         // 1
+    pub p1_conceded: bool,         // 1  — gave up revealing via forfeit_reveal
+    pub p2_conceded: bool,         // 1
+    pub p1_cheated: bool,          // 1  — set by finalize / claim_unrevealed_forfeit
+    pub p2_cheated: bool,          // 1
     pub winner: u8,                // 1 — 0=none, 1=P1, 2=P2
     pub bump: u8,                  // 1
 }
 
 impl Game {
     pub const SPACE: usize = 8      // discriminator
-        + 32 + 32                   // player1, player2
+        + 32 + 32 + 32 + 32         // player1, player2, invited, lobby
         + 32 + 32                   // board hashes
-        + 8 + 8 + 8 + 8 + 8 + 8    // bitmasks + revealed boards
-        + 8 + 8                     // wager, game_id
-        + 1 + 1 + 1 + 1 + 1        // hit counts, last_shot, turn_state, game_state
-        + 1 + 1 + 1 + 1;           // revealed flags, winner, bump
+        + 32                        // mint
+        + 16 * 7                    // bitmasks, revealed boards, pending salvo
+        + MAX_SHIPS * 3             // sunk cells, fleet
+        + 8 + 8 + 8                 // wager, bond, game_id
+        + 8 + 8 + 8                 // last_action_ts, turn_timeout_secs, reveal_deadline
+        + 1 + 1 + 1 + 1            // hit counts, sunk ship masks
+        + 1 + 1                     // fleet_size, total_ship_cells
+        + 1 + 1                     // board_rows, board_cols
+        + 1 + 1 + 1 + 1 + 1        // last_shot, turn_state, game_state, commitment/game mode
+        + 1 + 1 + 1 + 1 + 1 + 1    // revealed + cheated flags, winner, bump
+        + 1 + 1                     // conceded flags
+        + 1;                        // rated
+
+    /// Number of playable cells; valid cell indices are `0..board_cells()`.
+    pub fn board_cells(&self) -> u8 {
+        self.board_rows * self.board_cols
+    }
+
+    /// What each player escrows: their wager plus the honesty bond.
+    pub fn stake(&self) -> u64 {
+        self.wager + self.bond
+    }
+
+    /// Whether the game has been settled and its escrow paid out.
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.game_state,
+            game_state::FINISHED | game_state::DRAWN | game_state::VOIDED
+        )
+    }
+
+    /// Player (1 or 2) who owes the next fire or response.
+    pub fn player_on_move(&self) -> Option<u8> {
+        match self.turn_state {
+            turn_state::P1_FIRE | turn_state::P1_RESPOND => Some(1),
+            turn_state::P2_FIRE | turn_state::P2_RESPOND => Some(2),
+            _ => None,
+        }
+    }
+
+    /// True once the player on move has sat on it for longer than `turn_timeout_secs`.
+    pub fn turn_timed_out(&self, now: i64) -> bool {
+        now > self.last_action_ts.saturating_add(self.turn_timeout_secs)
+    }
+
+    /// Stops the battle with `winner` declared and opens the reveal window.
+    pub fn end_battle(&mut self, winner: u8, now: i64) {
+        self.withdraw_unanswered_shots();
+        self.winner = winner;
+        self.game_state = game_state::WAITING_REVEAL;
+        self.last_action_ts = now;
+        self.reveal_deadline = now.saturating_add(REVEAL_WINDOW_SECS);
+    }
+
+    /// Takes shots that are still awaiting a response out of the shooter's mask, for games
+    /// ended mid-turn, so finalize does not read the missing declarations as lies. Outside the
+    /// respond states no shot is owed and `last_shot_cell` may still be 255 (nothing fired).
+    pub fn withdraw_unanswered_shots(&mut self) {
+        match self.turn_state {
+            t if t == turn_state::P1_RESPOND => self.p2_shots &= !self.unanswered_shots(),
+            t if t == turn_state::P2_RESPOND => self.p1_shots &= !self.unanswered_shots(),
+            _ => {}
+        }
+        self.pending_shots = 0;
+    }
+
+    fn unanswered_shots(&self) -> u128 {
+        if self.game_mode == game_mode::SALVO {
+            self.pending_shots
+        } else {
+            1u128 << self.last_shot_cell
+        }
+    }
+
+    /// Ship lengths of this game's fleet, indexed by ship id.
+    pub fn ship_lengths(&self) -> &[u8] {
+        &self.fleet[..self.fleet_size as usize]
+    }
+}
+
+/// Global protocol settings, a singleton PDA created by the program's upgrade authority.
+#[account]
+pub struct Config {
+    pub admin: Pubkey,             // 32 — may change fee and treasury authority
+    pub treasury_authority: Pubkey, // 32 — may withdraw collected fees
+    pub fee_bps: u16,              // 2  — cut of every settled pot, in basis points
+    pub min_commit_frequency_ms: u32, // 4 — bounds on delegate_game's commit frequency
+    pub max_commit_frequency_ms: u32, // 4
+    /// ER validators games may be delegated to; empty allows any.
+    pub allowed_validators: Vec<Pubkey>, // 4 + 32 * MAX_ALLOWED_VALIDATORS
+    pub bump: u8,                  // 1
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 4 + 4 + (4 + 32 * MAX_ALLOWED_VALIDATORS) + 1;
+
+    /// Protocol fee taken from a pot of `pot`.
+    pub fn fee_for(&self, pot: u64) -> u64 {
+        (pot as u128 * self.fee_bps as u128 / 10_000) as u64
+    }
+
+    /// Whether a game may be delegated to `validator` (`None` lets the SDK pick).
+    pub fn allows_validator(&self, validator: Option<Pubkey>) -> bool {
+        if self.allowed_validators.is_empty() {
+            return true;
+        }
+        validator.is_some_and(|v| self.allowed_validators.contains(&v))
+    }
+}
+
+/// Program-owned fee sink. Lamport fees accrue on this account; token fees go to token
+/// accounts whose owner is this PDA.
+#[account]
+pub struct Treasury {
+    pub bump: u8,                  // 1
+}
+
+impl Treasury {
+    pub const SPACE: usize = 8 + 1;
+}
+
+/// Lets a short-lived ephemeral key sign ER moves for one player in one game.
+#[account]
+pub struct SessionToken {
+    pub authority: Pubkey,         // 32 — player the session acts for
+    pub game: Pubkey,              // 32 — the only game it may act in
+    pub session_key: Pubkey,       // 32 — ephemeral signer
+    pub expires_at: i64,           // 8
+    pub lamports_funded: u64,      // 8  — running total funded to session keys, capped
+    pub bump: u8,                  // 1
+}
+
+impl SessionToken {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// Per-wallet profile, created on a player's first `create_game` or settled game. Its
+/// counter assigns game ids, so a player's games are the PDAs for ids `0..games_created`;
+/// the rest is a lifetime record updated at settlement.
+#[account]
+pub struct PlayerProfile {
+    pub authority: Pubkey,         // 32
+    pub games_created: u64,        // 8  — id of the next game this player creates
+    pub games_played: u32,         // 4  — settled games
+    pub wins: u32,                 // 4
+    pub losses: u32,               // 4
+    pub draws: u32,                // 4  — drawn or voided games
+    pub cheats_detected: u32,      // 4  — games where this player was flagged as cheating
+    pub total_shots: u32,          // 4
+    pub total_hits: u32,           // 4  — shots the opponent declared as hits
+    pub lamports_won: u64,         // 8  — net gains from lamport games
+    pub lamports_lost: u64,        // 8  — net losses from lamport games
+    pub rating: u32,               // 4  — Elo, moved only by rated games
+    pub bump: u8,                  // 1
+}
+
+impl PlayerProfile {
+    pub const SPACE: usize = 8 + 32 + 8 + 4 * 7 + 8 + 8 + 4 + 1;
+
+    /// Fills in identity and the starting rating; a no-op for an existing profile.
+    pub fn init_if_new(&mut self, authority: Pubkey, bump: u8) {
+        self.authority = authority;
+        self.bump = bump;
+        if self.rating == 0 {
+            self.rating = INITIAL_RATING;
+        }
+    }
+
+    /// Adds a settled game to the record. `paid` is what this player received from escrow.
+    pub fn record_game(&mut self, game: &Game, is_player1: bool, paid: u64) {
+        let (seat, cheated, shots, hits) = if is_player1 {
+            (1, game.p1_cheated, game.p1_shots, game.p2_declared_hits)
+        } else {
+            (2, game.p2_cheated, game.p2_shots, game.p1_declared_hits)
+        };
+
+        self.games_played += 1;
+        match game.game_state {
+            game_state::FINISHED if game.winner == seat => self.wins += 1,
+            game_state::FINISHED => self.losses += 1,
+            _ => self.draws += 1,
+        }
+        if cheated {
+            self.cheats_detected += 1;
+        }
+        self.total_shots += shots.count_ones();
+        self.total_hits += hits.count_ones();
+
+        // Token games are tracked in their own mint, which this record has no unit for.
+        if game.mint == Pubkey::default() {
+            let staked = game.stake();
+            if paid >= staked {
+                self.lamports_won += paid - staked;
+            } else {
+                self.lamports_lost += staked - paid;
+            }
+        }
+    }
+
+    /// Records a settled game on both players' profiles (created lazily, hence the bumps)
+    /// and moves their ratings if the game was rated.
+    pub fn record_settlement(
+        (p1, p1_bump): (&mut PlayerProfile, u8),
+        (p2, p2_bump): (&mut PlayerProfile, u8),
+        game: &Game,
+        p1_paid: u64,
+        p2_paid: u64,
+    ) {
+        p1.init_if_new(game.player1, p1_bump);
+        p2.init_if_new(game.player2, p2_bump);
+        p1.record_game(game, true, p1_paid);
+        p2.record_game(game, false, p2_paid);
+        if game.rated {
+            rating::settle(&mut p1.rating, &mut p2.rating, game);
+        }
+    }
+
+    /// Share of shots that hit, in basis points.
+    pub fn accuracy_bps(&self) -> u32 {
+        if self.total_shots == 0 {
+            return 0;
+        }
+        (self.total_hits as u64 * 10_000 / self.total_shots as u64) as u32
+    }
+}
+
+/// Open game as listed in a lobby page, with enough of its ruleset to filter on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LobbyEntry {
+    pub game: Pubkey,              // 32
+    pub mint: Pubkey,              // 32 — default = lamport wager
+    pub wager: u64,                // 8
+    pub game_mode: u8,             // 1
+    pub commitment_mode: u8,       // 1
+    pub board_rows: u8,            // 1
+    pub board_cols: u8,            // 1
+    pub rated: bool,               // 1
+    pub bond: u64,                 // 8
+    pub fleet: [u8; MAX_SHIPS],    // 5  — ship lengths; only the first fleet_size are used
+    pub fleet_size: u8,            // 1
+    pub turn_timeout_secs: i64,    // 8
+}
+
+impl LobbyEntry {
+    pub const SPACE: usize = 32 + 32 + 8 + 1 + 1 + 1 + 1 + 1 + 8 + MAX_SHIPS + 1 + 8;
+}
+
+/// One page of open games in a wager bracket. Pages are PDAs
+/// `["lobby", bracket, page_le]`, so clients can walk a bracket page by page.
+#[account]
+pub struct LobbyPage {
+    pub bracket: u8,               // 1  — number of decimal digits of the wager
+    pub page: u16,                 // 2
+    pub entries: Vec<LobbyEntry>,  // 4 + LobbyEntry::SPACE * LOBBY_PAGE_SIZE
+    pub bump: u8,                  // 1
+}
+
+impl LobbyPage {
+    pub const SPACE: usize = 8 + 1 + 2 + (4 + LobbyEntry::SPACE * LOBBY_PAGE_SIZE) + 1;
+
+    /// Wager bracket: 0 for free games, otherwise the number of decimal digits, so
+    /// bracket 6 holds wagers of 100_000..=999_999 base units.
+    pub fn bracket_for(wager: u64) -> u8 {
+        match wager {
+            0 => 0,
+            w => w.ilog10() as u8 + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+
+    fn game(game_mode: u8, turn_state: u8) -> Game {
+        let mut game = Game::deserialize(&mut &[0u8; Game::SPACE - 8][..]).unwrap();
+        game.game_mode = game_mode;
+        game.turn_state = turn_state;
+        game.last_shot_cell = 255;
+        game
+    }
+
+    #[test]
+    fn no_shot_fired_withdraws_nothing() {
+        let mut fresh = game(game_mode::CLASSIC, turn_state::P1_FIRE);
+        fresh.withdraw_unanswered_shots();
+        assert_eq!((fresh.p1_shots, fresh.p2_shots), (0, 0));
+
+        let mut answered = game(game_mode::CLASSIC, turn_state::P2_FIRE);
+        answered.p1_shots = 0b101;
+        answered.withdraw_unanswered_shots();
+        assert_eq!(answered.p1_shots, 0b101);
+    }
+
+    #[test]
+    fn classic_withdraws_the_last_shot() {
+        let mut game = game(game_mode::CLASSIC, turn_state::P2_RESPOND);
+        game.p1_shots = 0b1001;
+        game.last_shot_cell = 3;
+        game.withdraw_unanswered_shots();
+        assert_eq!(game.p1_shots, 0b0001);
+    }
+
+    #[test]
+    fn salvo_withdraws_the_pending_volley() {
+        let mut game = game(game_mode::SALVO, turn_state::P1_RESPOND);
+        game.p2_shots = 0b1111;
+        game.pending_shots = 0b0110;
+        game.withdraw_unanswered_shots();
+        assert_eq!((game.p2_shots, game.pending_shots), (0b1001, 0));
+    }
 }
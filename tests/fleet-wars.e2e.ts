@@ -68,8 +68,10 @@ function makeWallet(keypair: Keypair): WalletLike {
 }
 
 function boardHash(board: bigint, salt: Uint8Array): number[] {
-  const boardBuf = Buffer.alloc(8);
-  boardBuf.writeBigUInt64LE(board);
+  // Boards are u128 bitmasks, hashed little-endian.
+  const boardBuf = Buffer.alloc(16);
+  boardBuf.writeBigUInt64LE(board & 0xffff_ffff_ffff_ffffn, 0);
+  boardBuf.writeBigUInt64LE(board >> 64n, 8);
   const hash = createHash("sha256").update(boardBuf).update(Buffer.from(salt)).digest();
   return Array.from(hash);
 }
@@ -100,20 +102,33 @@ describe("fleet-wars devnet ER e2e", () => {
 
   const program = new Program(idl as FleetWars, provider);
 
-  const gameId = new BN(Date.now());
-  const gameIdLe = Buffer.alloc(8);
-  gameIdLe.writeBigUInt64LE(BigInt(gameId.toString()));
-
-  const [gamePda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("game"), player1.publicKey.toBuffer(), gameIdLe],
+  const [profilePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("profile"), player1.publicKey.toBuffer()],
     program.programId
   );
 
+  // Game ids come from player1's profile counter; resolved before create_game.
+  let gameId: BN;
+  let gamePda: PublicKey;
+
   const p1Board = (1n << 0n) | (1n << 1n) | (1n << 2n) | (1n << 3n) | (1n << 4n) | (1n << 5n) | (1n << 6n) | (1n << 7n) | (1n << 8n);
   const p2Board = p1Board;
   const p1Salt = Uint8Array.from({ length: 32 }, (_, i) => i + 1);
   const p2Salt = Uint8Array.from({ length: 32 }, (_, i) => i + 101);
   const wager = new BN(500_000); // 0.0005 SOL each
+  // Boards 0..8 decompose into ship 0 = {0, 8}, ship 1 = {1, 2, 3}, ship 2 = {4, 5, 6, 7};
+  // P1 fires at cells 0..8 in order, so each ship sinks on its last cell.
+  const sunkAtRound: Record<number, number> = { 3: 1, 7: 2, 8: 0 };
+  const rules = {
+    turnTimeoutSecs: new BN(300),
+    commitmentMode: 0, // flat SHA256 board hash
+    fleet: Buffer.from([2, 3, 4]),
+    boardRows: 8,
+    boardCols: 8,
+    gameMode: 0, // classic, one shot per turn
+    bond: new BN(100_000), // returned to each honest player at finalize
+    rated: true, // finalize moves both players' Elo ratings
+  };
 
   it("funds player2", async () => {
     const fundTx = new Transaction().add(
@@ -134,13 +149,56 @@ describe("fleet-wars devnet ER e2e", () => {
     expect(bal).to.be.greaterThan(0);
   });
 
+  it("initializes protocol config if missing", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+    const existing = await baseConnection.getAccountInfo(configPda, "confirmed");
+    if (existing) return;
+
+    // Only the upgrade authority may create it, so this assumes player1 deployed the program.
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    const initTx = await program.methods
+      .initConfig(player1.publicKey, 100) // 1% fee
+      .accounts({
+        admin: player1.publicKey,
+        programData,
+      } as any)
+      .transaction();
+    initTx.feePayer = player1.publicKey;
+    await sendAndConfirmTransaction(baseConnection, initTx, [player1], {
+      commitment: "confirmed",
+      skipPreflight: true,
+    });
+  });
+
   it("creates and joins game on base layer", async () => {
+    const profile = await (program.account as any).playerProfile.fetchNullable(profilePda);
+    gameId = profile ? profile.gamesCreated : new BN(0);
+    [gamePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game"), player1.publicKey.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
     const createTx = await program.methods
-      .createGame(gameId, boardHash(p1Board, p1Salt), wager)
+      // invite-only: nobody but player2 may join
+      .createGame(boardHash(p1Board, p1Salt), wager, rules, player2.publicKey)
       .accounts({
+        profile: profilePda,
         game: gamePda,
         player1: player1.publicKey,
         systemProgram: SystemProgram.programId,
+        // lamport wager: no SPL mint or vault
+        mint: null,
+        vault: null,
+        player1Token: null,
+        tokenProgram: null,
+        // invite-only games are not listed in a lobby page
+        lobby: null,
       })
       .transaction();
 
@@ -156,6 +214,10 @@ describe("fleet-wars devnet ER e2e", () => {
         game: gamePda,
         player2: player2.publicKey,
         systemProgram: SystemProgram.programId,
+        vault: null,
+        player2Token: null,
+        lobby: null,
+        tokenProgram: null,
       })
       .transaction();
 
@@ -179,9 +241,9 @@ describe("fleet-wars devnet ER e2e", () => {
     const delegationMetadataPda = delegationMetadataPdaFromDelegatedAccount(gamePda);
 
     const delegateTx = await program.methods
-      .delegateGame(gameId)
+      .delegateGame(gameId, 5_000) // commit every 5s, within the config bounds
       .accounts({
-        player1: player1.publicKey,
+        player: player1.publicKey, // either player may delegate
         validator: ER_VALIDATOR,
         bufferPda,
         delegationRecordPda,
@@ -214,7 +276,7 @@ describe("fleet-wars devnet ER e2e", () => {
     for (let round = 0; round < 9; round++) {
       const p1Fire = await program.methods
         .fireShot(round)
-        .accounts({ game: gamePda, player: player1.publicKey })
+        .accounts({ game: gamePda, player: player1.publicKey, session: null })
         .transaction();
       p1Fire.feePayer = player1.publicKey;
       await sendAndConfirmMagicTransaction(routerConnection, p1Fire, [player1], {
@@ -223,10 +285,16 @@ describe("fleet-wars devnet ER e2e", () => {
       });
 
       const p2Respond = await program.methods
-        .respondShot(true)
+        .respondShot(
+          round in sunkAtRound ? { sunk: { shipId: sunkAtRound[round] } } : { hit: {} },
+          null
+        )
         .accounts({
           game: gamePda,
           player: player2.publicKey,
+          session: null,
+          magicContext: MAGIC_CONTEXT_ID,
+          magicProgram: MAGIC_PROGRAM_ID,
         })
         .transaction();
       p2Respond.feePayer = player2.publicKey;
@@ -238,7 +306,7 @@ describe("fleet-wars devnet ER e2e", () => {
       if (round < 8) {
         const p2Fire = await program.methods
           .fireShot(63 - round)
-          .accounts({ game: gamePda, player: player2.publicKey })
+          .accounts({ game: gamePda, player: player2.publicKey, session: null })
           .transaction();
         p2Fire.feePayer = player2.publicKey;
         await sendAndConfirmMagicTransaction(routerConnection, p2Fire, [player2], {
@@ -247,10 +315,13 @@ describe("fleet-wars devnet ER e2e", () => {
         });
 
         const p1Respond = await program.methods
-          .respondShot(false)
+          .respondShot({ miss: {} }, null)
           .accounts({
             game: gamePda,
             player: player1.publicKey,
+            session: null,
+            magicContext: MAGIC_CONTEXT_ID,
+            magicProgram: MAGIC_PROGRAM_ID,
           })
           .transaction();
         p1Respond.feePayer = player1.publicKey;
@@ -261,21 +332,7 @@ describe("fleet-wars devnet ER e2e", () => {
       }
     }
 
-    const endSessionTx = await program.methods
-      .endSession()
-      .accounts({
-        game: gamePda,
-        player: player1.publicKey,
-        magicContext: MAGIC_CONTEXT_ID,
-        magicProgram: MAGIC_PROGRAM_ID,
-      })
-      .transaction();
-    endSessionTx.feePayer = player1.publicKey;
-    await sendAndConfirmMagicTransaction(routerConnection, endSessionTx, [player1], {
-      commitment: "confirmed",
-      skipPreflight: true,
-    });
-
+    // The final respondShot committed and undelegated the game; no endSession needed.
     // Wait for delegation record to be removed AND account owner to revert to program
     let undelegated = false;
     for (let i = 0; i < 60; i++) {
@@ -334,8 +391,20 @@ describe("fleet-wars devnet ER e2e", () => {
       .finalize()
       .accounts({
         game: gamePda,
-        winner: player1.publicKey,
+        p1Payout: player1.publicKey,
+        p2Payout: player2.publicKey,
         caller: player1.publicKey,
+        // lifetime stats; player2's profile is created here on first settlement
+        p1Profile: profilePda,
+        p2Profile: PublicKey.findProgramAddressSync(
+          [Buffer.from("profile"), player2.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        player1: null,
+        // lamport game: the fee lands on the treasury PDA itself
+        treasuryToken: null,
+        vault: null,
+        tokenProgram: null,
       })
       .transaction();
     finalizeTx.feePayer = player1.publicKey;